@@ -0,0 +1,263 @@
+use base64::Engine;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+/// Arquivos embutidos no binário, servidos em `asset://localhost/{caminho}`
+const BUNDLED: &[(&str, &[u8])] = &[
+    ("icons/128x128.png", include_bytes!("../icons/128x128.png")),
+    ("ui/styles.css", include_bytes!("../ui/styles.css")),
+    ("ui/common.js", include_bytes!("../ui/common.js")),
+    ("ui/toolbar/index.html", include_bytes!("../ui/toolbar/index.html")),
+    ("ui/content/index.html", include_bytes!("../ui/content/index.html")),
+    ("ui/content/add-profile.html", include_bytes!("../ui/content/add-profile.html")),
+    ("ui/content/edit-profile.html", include_bytes!("../ui/content/edit-profile.html")),
+    ("ui/content/settings.html", include_bytes!("../ui/content/settings.html")),
+    ("ui/content/downloads.html", include_bytes!("../ui/content/downloads.html")),
+    ("ui/content/palette.html", include_bytes!("../ui/content/palette.html")),
+];
+
+/// Host usado nas URLs das páginas embutidas
+const HOST: &str = "localhost";
+
+/// Extensões que podem ser lidas do diretório de dados. O restante do
+/// diretório (profiles.json, cookies, bancos das webviews) fica inacessível.
+const DATA_DIR_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "svg", "ico"];
+
+/// Política para arquivos do diretório de dados: um SVG baixado (favicon,
+/// ícone de receita do Ferdium) não pode rodar scripts na origem da aplicação
+pub const DATA_DIR_CSP: &str = "sandbox; script-src 'none'";
+
+/// Motivo de uma requisição `asset://` não ser atendida
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetError {
+    /// Caminho fora das áreas permitidas (403)
+    Forbidden,
+    /// Caminho permitido, mas inexistente (404)
+    NotFound,
+}
+
+impl AssetError {
+    pub fn status(self) -> u16 {
+        match self {
+            AssetError::Forbidden => 403,
+            AssetError::NotFound => 404,
+        }
+    }
+}
+
+/// Conteúdo de um asset resolvido
+#[derive(Debug)]
+pub struct Asset {
+    pub mime_type: &'static str,
+    pub content: Cow<'static, [u8]>,
+    /// Veio do diretório de dados e deve ser servido com `DATA_DIR_CSP`
+    pub untrusted: bool,
+}
+
+/// URL de um arquivo embutido, como `ui/content/settings.html`.
+/// No Windows o WebView2 expõe protocolos customizados como `http://{esquema}.localhost`.
+pub fn url(path: &str) -> String {
+    if cfg!(target_os = "windows") {
+        format!("http://asset.{}/{}", HOST, path)
+    } else {
+        format!("asset://{}/{}", HOST, path)
+    }
+}
+
+/// Se a URI aponta para a interface embutida (`asset://localhost/ui/...`), ou
+/// seja, se a página é confiável para enviar mensagens IPC da aplicação.
+/// Imagens do diretório de dados usam o mesmo host, mas não são confiáveis.
+pub fn is_app_uri(uri: &http::Uri) -> bool {
+    let host = uri.host().unwrap_or_default();
+    let app_host = match uri.scheme_str() {
+        Some("asset") => host == HOST,
+        Some("http") | Some("https") => cfg!(target_os = "windows") && host == format!("asset.{}", HOST),
+        _ => false,
+    };
+    app_host && uri.path().starts_with("/ui/")
+}
+
+/// Caminho pedido em uma URI do protocolo. URLs sem o host `localhost`
+/// (`asset://profiles/...`) usam o host como primeiro segmento do caminho.
+pub fn request_path(uri: &http::Uri) -> String {
+    match uri.host() {
+        Some(host) if host != HOST && host != format!("asset.{}", HOST) => format!("{}{}", host, uri.path()),
+        _ => uri.path().to_string(),
+    }
+}
+
+/// Resolve o caminho de uma URL `asset://` para um arquivo embutido ou para
+/// uma imagem dentro de `data_dir`.
+///
+/// O caminho é decodificado e validado antes de tocar o disco: segmentos `.`
+/// e `..`, barras invertidas e separadores de unidade são recusados, e o
+/// caminho canônico (com links simbólicos resolvidos) precisa continuar
+/// dentro de `data_dir`.
+pub fn resolve(data_dir: &Path, request_path: &str) -> Result<Asset, AssetError> {
+    let relative = sanitize_path(request_path)?;
+
+    let key = relative.join("/");
+    if let Some((_, content)) = BUNDLED.iter().find(|(name, _)| *name == key) {
+        return Ok(Asset {
+            mime_type: mime_type(&key),
+            content: Cow::Borrowed(content),
+            untrusted: false,
+        });
+    }
+
+    let path: PathBuf = relative.iter().fold(data_dir.to_path_buf(), |path, segment| path.join(segment));
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    if !DATA_DIR_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(AssetError::Forbidden);
+    }
+
+    let root = data_dir.canonicalize().map_err(|_| AssetError::NotFound)?;
+    let path = path.canonicalize().map_err(|_| AssetError::NotFound)?;
+    if !path.starts_with(&root) {
+        return Err(AssetError::Forbidden);
+    }
+
+    let content = std::fs::read(&path).map_err(|_| AssetError::NotFound)?;
+    Ok(Asset {
+        mime_type: mime_type(&key),
+        content: Cow::Owned(content),
+        untrusted: true,
+    })
+}
+
+/// Divide o caminho em segmentos seguros para juntar a um diretório base
+fn sanitize_path(request_path: &str) -> Result<Vec<String>, AssetError> {
+    let decoded = percent_encoding::percent_decode_str(request_path)
+        .decode_utf8()
+        .map_err(|_| AssetError::Forbidden)?;
+
+    let segments: Vec<String> = decoded
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(String::from)
+        .collect();
+
+    let unsafe_segment = |segment: &String| {
+        segment == "." || segment == ".." || segment.contains(['\\', ':', '\0'])
+    };
+    if segments.is_empty() || segments.iter().any(unsafe_segment) {
+        return Err(AssetError::Forbidden);
+    }
+    Ok(segments)
+}
+
+pub fn mime_type(path: &str) -> &'static str {
+    let extension = path.rsplit_once('.').map(|(_, ext)| ext.to_lowercase()).unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "html" => "text/html",
+        "css" => "text/css",
+        "js" => "text/javascript",
+        _ => "application/octet-stream",
+    }
+}
+
+/// `data:` URL com o conteúdo do arquivo, para pré-visualizar arquivos
+/// escolhidos pelo usuário fora do diretório de dados
+pub fn data_url(path: &Path) -> std::io::Result<String> {
+    let content = std::fs::read(path)?;
+    Ok(format!(
+        "data:{};base64,{}",
+        mime_type(&path.to_string_lossy()),
+        base64::engine::general_purpose::STANDARD.encode(content)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("feather-alloy-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_resolve_allowed_assets() {
+        let root = temp_dir();
+        let data_dir = root.join("data");
+        std::fs::create_dir_all(data_dir.join("profiles/abc")).unwrap();
+        std::fs::write(data_dir.join("profiles/abc/icon.png"), b"png").unwrap();
+
+        let icon = resolve(&data_dir, "profiles/abc/icon.png").unwrap();
+        assert_eq!(icon.mime_type, "image/png");
+        assert_eq!(&*icon.content, b"png");
+        assert!(icon.untrusted);
+        assert!(resolve(&data_dir, "/profiles/abc/icon.png").is_ok());
+
+        let bundled = resolve(&data_dir, "icons/128x128.png").unwrap();
+        assert!(!bundled.content.is_empty());
+        assert!(!bundled.untrusted);
+        assert_eq!(resolve(&data_dir, "ui/content/settings.html").unwrap().mime_type, "text/html");
+        assert_eq!(resolve(&data_dir, "ui/styles.css").unwrap().mime_type, "text/css");
+        // Arquivos de ui/ fora do pacote não são lidos do disco
+        assert_eq!(resolve(&data_dir, "ui/content/missing.html").unwrap_err(), AssetError::Forbidden);
+
+        let uri: http::Uri = "asset://localhost/ui/content/edit-profile.html?uuid=abc".parse().unwrap();
+        assert_eq!(request_path(&uri), "/ui/content/edit-profile.html");
+        let legacy: http::Uri = "asset://profiles/abc/icon.png".parse().unwrap();
+        assert_eq!(request_path(&legacy), "profiles/abc/icon.png");
+
+        assert!(is_app_uri(&url("ui/content/index.html").parse().unwrap()));
+        assert!(!is_app_uri(&legacy));
+        // Imagens do diretório de dados (ex.: SVG baixado) não falam com o backend
+        assert!(!is_app_uri(&url("profiles/abc/favicon.svg").parse().unwrap()));
+        assert!(!is_app_uri(&"https://example.com/ui/content/index.html".parse().unwrap()));
+
+        assert_eq!(resolve(&data_dir, "profiles/abc/missing.png").unwrap_err(), AssetError::NotFound);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_rejects_traversal() {
+        let root = temp_dir();
+        let data_dir = root.join("data");
+        std::fs::create_dir_all(data_dir.join("profiles/abc")).unwrap();
+        std::fs::write(root.join("secret.png"), b"secret").unwrap();
+        std::fs::write(data_dir.join("profiles.json"), b"[]").unwrap();
+        std::fs::write(data_dir.join("profiles/abc/cookies"), b"sid").unwrap();
+
+        for path in [
+            "../secret.png",
+            "profiles/../../secret.png",
+            "profiles/%2e%2e/%2E%2E/secret.png",
+            "profiles/..%2f..%2fsecret.png",
+            "profiles\\..\\..\\secret.png",
+            "C:/Windows/win.ini",
+            "",
+            "/",
+        ] {
+            assert_eq!(resolve(&data_dir, path).unwrap_err(), AssetError::Forbidden, "{}", path);
+        }
+
+        // Fora da lista de extensões: dados de sessão e configurações
+        assert_eq!(resolve(&data_dir, "profiles.json").unwrap_err(), AssetError::Forbidden);
+        assert_eq!(resolve(&data_dir, "profiles/abc/cookies").unwrap_err(), AssetError::Forbidden);
+        // Caminho absoluto é tratado como relativo ao diretório de dados
+        assert_eq!(resolve(&data_dir, "/etc/passwd").unwrap_err(), AssetError::Forbidden);
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(root.join("secret.png"), data_dir.join("profiles/abc/icon.png")).unwrap();
+            assert_eq!(resolve(&data_dir, "profiles/abc/icon.png").unwrap_err(), AssetError::Forbidden);
+        }
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,5 +1,7 @@
 use muda::{Menu, MenuItem, MenuEvent, PredefinedMenuItem, ContextMenu};
 
+use crate::Error;
+
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProfileMenuAction {
@@ -18,7 +20,7 @@ pub struct ProfileContextMenu {
 }
 
 impl ProfileContextMenu {
-    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new() -> Result<Self, Error> {
         let menu = Menu::new();
         
         let reload_item = MenuItem::new("Atualizar conteúdo", true, None);
@@ -41,7 +43,7 @@ impl ProfileContextMenu {
         })
     }
     
-    pub fn show_at(&self, window: &tao::window::Window, x: f64, y: f64) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn show_at(&self, window: &tao::window::Window, x: f64, y: f64) -> Result<(), Error> {
         let position = muda::dpi::Position::Logical((x, y).into());
         
         #[cfg(target_os = "windows")]
@@ -0,0 +1,947 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use regex::Regex;
+
+use crate::paths::Paths;
+use crate::profile::{normalize_user_agent, AppSettings, AppState, ProfileUpdate, WebProfile, Workspace, Workspaces};
+use crate::shortcuts::ShortcutAction;
+use crate::Error;
+
+/// Onde o controlador grava perfis, workspaces, configurações e ícones
+pub trait Storage {
+    fn save_profiles(&self, profiles: &[WebProfile]) -> Result<(), Error>;
+    fn save_workspaces(&self, workspaces: &Workspaces) -> Result<(), Error>;
+    fn save_settings(&self, settings: &AppSettings) -> Result<(), Error>;
+    /// Copia o ícone para a pasta do perfil e retorna o caminho relativo
+    fn save_profile_icon(&self, uuid: &str, source_path: &str) -> Result<String, Error>;
+    fn delete_profile_icon(&self, uuid: &str) -> Result<(), Error>;
+    fn delete_profile_data(&self, uuid: &str) -> Result<(), Error>;
+}
+
+/// Armazenamento no diretório de dados da aplicação
+pub struct DiskStorage {
+    paths: Paths,
+}
+
+impl DiskStorage {
+    pub fn new(paths: Paths) -> Self {
+        Self { paths }
+    }
+}
+
+impl Storage for DiskStorage {
+    fn save_profiles(&self, profiles: &[WebProfile]) -> Result<(), Error> {
+        crate::persistence::save_profiles(&self.paths, profiles)
+    }
+
+    fn save_workspaces(&self, workspaces: &Workspaces) -> Result<(), Error> {
+        crate::persistence::save_workspaces(&self.paths, workspaces)
+    }
+
+    fn save_settings(&self, settings: &AppSettings) -> Result<(), Error> {
+        crate::persistence::save_settings(&self.paths, settings)
+    }
+
+    fn save_profile_icon(&self, uuid: &str, source_path: &str) -> Result<String, Error> {
+        crate::persistence::save_profile_icon(&self.paths, uuid, source_path)
+    }
+
+    fn delete_profile_icon(&self, uuid: &str) -> Result<(), Error> {
+        crate::persistence::delete_profile_icon(&self.paths, uuid)
+    }
+
+    fn delete_profile_data(&self, uuid: &str) -> Result<(), Error> {
+        crate::persistence::delete_profile_data(&self.paths, uuid)
+    }
+}
+
+/// Efeitos na interface pedidos pelo controlador, aplicados pelo `WindowManager`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViewCommand {
+    /// Reenviar a lista de perfis para a toolbar
+    RefreshToolbar,
+    /// Reenviar os workspaces para a toolbar e para a página de conteúdo
+    RefreshWorkspaces,
+    /// Voltar para a tela inicial
+    ShowWelcome,
+    /// Descartar a webview do perfil; ela é recriada na próxima exibição
+    RebuildProfileView { uuid: String },
+    /// Descartar webview, WebContext e estado de exibição de um perfil removido
+    ForgetProfile { uuid: String },
+    /// Buscar o favicon do site em segundo plano
+    FetchFavicon { uuid: String, url: String },
+    /// Descartar as webviews destes perfis, mantendo a sessão
+    HibernateProfiles(Vec<String>),
+    /// Exibir o perfil, trazendo a janela para frente
+    ActivateProfile { uuid: String },
+    /// Recarregar a página do perfil
+    ReloadProfile { uuid: String },
+    ShowSettings,
+    /// Abrir a paleta de comandos na área de conteúdo
+    ShowPalette,
+    /// Reenviar os atalhos de teclado para as webviews abertas
+    RefreshShortcuts,
+    /// Trocar o atalho global de mostrar/ocultar a janela
+    RegisterGlobalHotkey,
+    SetVerboseLogging(bool),
+    ShowError(String),
+    ShowNotice(String),
+}
+
+/// O que fazer quando o usuário fecha a janela
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseAction {
+    Hide,
+    Minimize,
+    Exit,
+}
+
+/// Regras de perfis e configurações, sem depender de janela ou webviews.
+///
+/// Cada operação altera o `AppState`, grava pelo `Storage` e retorna os
+/// comandos que a interface deve executar, na ordem em que devem ser aplicados.
+pub struct AppController {
+    state: AppState,
+    storage: Box<dyn Storage>,
+    // Padrões de não lidas já compilados, por perfil
+    unread_patterns: Mutex<HashMap<String, Vec<Regex>>>,
+}
+
+impl AppController {
+    pub fn new(state: AppState, storage: Box<dyn Storage>) -> Self {
+        Self { state, storage, unread_patterns: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn add_profile(
+        &self,
+        name: String,
+        url: String,
+        icon_path: Option<String>,
+        user_agent: Option<String>,
+    ) -> Vec<ViewCommand> {
+        let mut commands = Vec::new();
+        let mut profile = WebProfile::new(name, url, icon_path, normalize_user_agent(user_agent));
+
+        // Ícone escolhido pelo usuário é copiado para a pasta do perfil;
+        // sem ícone, tentamos o favicon do site
+        match profile.icon_path.clone() {
+            Some(path) => match self.storage.save_profile_icon(&profile.uuid, &path) {
+                Ok(new_path) => {
+                    log::info!("Icon saved for new profile: {}", new_path);
+                    profile.icon_path = Some(new_path);
+                }
+                Err(e) => commands.push(ViewCommand::ShowError(format!(
+                    "Não foi possível salvar o ícone de {}: {}",
+                    profile.name, e
+                ))),
+            },
+            None => commands.push(ViewCommand::FetchFavicon {
+                uuid: profile.uuid.clone(),
+                url: profile.url.clone(),
+            }),
+        }
+
+        log::info!("Profile added: {} ({})", profile.name, profile.url);
+        let mut data = self.state.lock().unwrap();
+        // Perfis novos entram no workspace que está sendo exibido
+        profile.workspaces = data.workspaces.active.iter().cloned().collect();
+        data.profiles.push(profile);
+        commands.extend(self.save_profiles(&data.profiles));
+        drop(data);
+
+        commands.push(ViewCommand::RefreshToolbar);
+        commands.push(ViewCommand::ShowWelcome);
+        commands
+    }
+
+    pub fn update_profile(&self, uuid: &str, changes: ProfileUpdate) -> Result<Vec<ViewCommand>, Error> {
+        let ProfileUpdate {
+            name,
+            url,
+            icon_path,
+            user_agent,
+            auto_hibernate,
+            unread_patterns,
+            notifications_muted,
+            allowed_domains,
+            enabled,
+            workspaces,
+        } = changes;
+
+        // Padrões inválidos recusam o formulário antes de qualquer alteração
+        let unread_patterns = trimmed(unread_patterns);
+        let compiled_patterns = crate::unread::compile_patterns(&unread_patterns)?;
+
+        let mut commands = Vec::new();
+        let mut data = self.state.lock().unwrap();
+        let workspaces: Vec<String> = workspaces
+            .into_iter()
+            .filter(|workspace| data.workspaces.exists(workspace))
+            .collect();
+        let profile = data
+            .profiles
+            .iter_mut()
+            .find(|p| p.uuid == uuid)
+            .ok_or_else(|| Error::ProfileNotFound(uuid.to_string()))?;
+
+        profile.name = name;
+        profile.auto_hibernate = auto_hibernate;
+        profile.notifications_muted = notifications_muted;
+        let disabled = profile.enabled && !enabled;
+        profile.enabled = enabled;
+        profile.unread_patterns = unread_patterns;
+        self.unread_patterns.lock().unwrap().insert(uuid.to_string(), compiled_patterns);
+        profile.workspaces = workspaces;
+        let allowed_domains = trimmed(allowed_domains);
+        // As regras de links dependem da URL e dos domínios permitidos
+        let links_changed = profile.url != url || profile.allowed_domains != allowed_domains;
+        profile.allowed_domains = allowed_domains;
+        let url_changed = profile.url != url;
+        profile.url = url;
+
+        if profile.icon_path != icon_path {
+            match icon_path {
+                // Ícones já copiados para a pasta do perfil são mantidos como estão
+                Some(path) if is_profile_file(uuid, &path) => profile.icon_path = Some(path),
+                Some(path) => match self.storage.save_profile_icon(uuid, &path) {
+                    Ok(new_path) => {
+                        log::info!("Icon updated for profile {}: {}", uuid, new_path);
+                        profile.icon_path = Some(new_path);
+                    }
+                    Err(e) => {
+                        commands.push(ViewCommand::ShowError(format!("Não foi possível salvar o ícone: {}", e)));
+                        profile.icon_path = Some(path);
+                    }
+                },
+                // Ícone removido: volta a usar o favicon do site
+                None => {
+                    if let Err(e) = self.storage.delete_profile_icon(uuid) {
+                        log::warn!("Failed to delete icon: {}", e);
+                    }
+                    profile.icon_path = None;
+                    commands.push(ViewCommand::FetchFavicon { uuid: uuid.to_string(), url: profile.url.clone() });
+                }
+            }
+        } else if url_changed && profile.icon_path.is_none() {
+            commands.push(ViewCommand::FetchFavicon { uuid: uuid.to_string(), url: profile.url.clone() });
+        }
+
+        let user_agent = normalize_user_agent(user_agent);
+        let needs_rebuild = links_changed || profile.user_agent != user_agent;
+        profile.user_agent = user_agent;
+        log::info!("Profile updated: {} ({})", profile.name, profile.url);
+
+        commands.extend(self.save_profiles(&data.profiles));
+        drop(data);
+
+        // User-Agent e regras de links só podem ser definidos na criação da
+        // webview; perfis desativados não mantêm a webview carregada
+        if needs_rebuild || disabled {
+            commands.push(ViewCommand::RebuildProfileView { uuid: uuid.to_string() });
+        }
+        commands.push(ViewCommand::RefreshToolbar);
+        commands.push(ViewCommand::ShowWelcome);
+        Ok(commands)
+    }
+
+    pub fn remove_profile(&self, uuid: &str) -> Result<Vec<ViewCommand>, Error> {
+        let mut data = self.state.lock().unwrap();
+        let count = data.profiles.len();
+        data.profiles.retain(|p| p.uuid != uuid);
+        if data.profiles.len() == count {
+            return Err(Error::ProfileNotFound(uuid.to_string()));
+        }
+
+        let mut commands = vec![ViewCommand::ForgetProfile { uuid: uuid.to_string() }];
+        commands.extend(self.save_profiles(&data.profiles));
+        drop(data);
+        self.unread_patterns.lock().unwrap().remove(uuid);
+
+        if let Err(e) = self.storage.delete_profile_data(uuid) {
+            commands.push(ViewCommand::ShowError(format!("Não foi possível apagar os dados do perfil: {}", e)));
+        }
+        log::info!("Profile {} removed", uuid);
+
+        commands.push(ViewCommand::RefreshToolbar);
+        commands.push(ViewCommand::ShowWelcome);
+        Ok(commands)
+    }
+
+    /// Reordena os perfis arrastados na barra lateral. Os perfis listados trocam de
+    /// lugar entre si; os demais (ex.: de outros workspaces) mantêm a posição.
+    pub fn reorder_profiles(&self, uuids: &[String]) -> Vec<ViewCommand> {
+        let mut data = self.state.lock().unwrap();
+        let rank = |profile: &WebProfile| uuids.iter().position(|uuid| *uuid == profile.uuid);
+
+        let slots: Vec<usize> = (0..data.profiles.len()).filter(|&i| rank(&data.profiles[i]).is_some()).collect();
+        let mut moved: Vec<WebProfile> = slots.iter().map(|&i| data.profiles[i].clone()).collect();
+        moved.sort_by_key(|profile| rank(profile));
+        if moved.iter().zip(&slots).all(|(profile, &i)| profile.uuid == data.profiles[i].uuid) {
+            return Vec::new();
+        }
+        for (profile, i) in moved.into_iter().zip(slots) {
+            data.profiles[i] = profile;
+        }
+
+        let mut commands = self.save_profiles(&data.profiles);
+        drop(data);
+        log::info!("Profiles reordered");
+
+        commands.push(ViewCommand::RefreshToolbar);
+        commands
+    }
+
+    /// Pedido de atualizar o ícone a partir do favicon do site
+    pub fn refresh_profile_icon(&self, uuid: &str) -> Result<Vec<ViewCommand>, Error> {
+        let data = self.state.lock().unwrap();
+        let profile = data
+            .profiles
+            .iter()
+            .find(|p| p.uuid == uuid)
+            .ok_or_else(|| Error::ProfileNotFound(uuid.to_string()))?;
+        Ok(vec![ViewCommand::FetchFavicon { uuid: uuid.to_string(), url: profile.url.clone() }])
+    }
+
+    /// Aplica o favicon baixado. Perfis removidos durante o download são ignorados.
+    pub fn apply_favicon(&self, uuid: &str, path: String) -> Vec<ViewCommand> {
+        let mut data = self.state.lock().unwrap();
+        let Some(profile) = data.profiles.iter_mut().find(|p| p.uuid == uuid) else {
+            return Vec::new();
+        };
+        log::info!("Favicon applied for {}: {}", uuid, path);
+        profile.icon_path = Some(path);
+
+        let mut commands = self.save_profiles(&data.profiles);
+        commands.push(ViewCommand::RefreshToolbar);
+        commands
+    }
+
+    pub fn add_workspace(&self, name: &str) -> Result<Vec<ViewCommand>, Error> {
+        let workspace = Workspace::new(workspace_name(name)?);
+        log::info!("Workspace added: {}", workspace.name);
+
+        let mut data = self.state.lock().unwrap();
+        data.workspaces.workspaces.push(workspace);
+        Ok(self.save_workspaces(&data.workspaces))
+    }
+
+    pub fn rename_workspace(&self, uuid: &str, name: &str) -> Result<Vec<ViewCommand>, Error> {
+        let name = workspace_name(name)?;
+        let mut data = self.state.lock().unwrap();
+        let workspace = data
+            .workspaces
+            .workspaces
+            .iter_mut()
+            .find(|w| w.uuid == uuid)
+            .ok_or_else(|| Error::WorkspaceNotFound(uuid.to_string()))?;
+        workspace.name = name;
+        Ok(self.save_workspaces(&data.workspaces))
+    }
+
+    /// Remove o workspace. Os perfis continuam existindo, só perdem a associação.
+    pub fn remove_workspace(&self, uuid: &str) -> Result<Vec<ViewCommand>, Error> {
+        let mut data = self.state.lock().unwrap();
+        if !data.workspaces.exists(uuid) {
+            return Err(Error::WorkspaceNotFound(uuid.to_string()));
+        }
+
+        data.workspaces.workspaces.retain(|w| w.uuid != uuid);
+        if data.workspaces.active.as_deref() == Some(uuid) {
+            data.workspaces.active = None;
+        }
+
+        let mut commands = Vec::new();
+        if data.profiles.iter().any(|p| p.workspaces.iter().any(|w| w == uuid)) {
+            for profile in &mut data.profiles {
+                profile.workspaces.retain(|w| w != uuid);
+            }
+            commands.extend(self.save_profiles(&data.profiles));
+            commands.push(ViewCommand::RefreshToolbar);
+        }
+        log::info!("Workspace {} removed", uuid);
+
+        commands.extend(self.save_workspaces(&data.workspaces));
+        Ok(commands)
+    }
+
+    /// Troca o workspace exibido (None = todos os perfis). Se o perfil aberto
+    /// ficar de fora, volta para a tela inicial.
+    pub fn switch_workspace(&self, uuid: Option<String>, current_profile: Option<&str>) -> Result<Vec<ViewCommand>, Error> {
+        let mut data = self.state.lock().unwrap();
+        if let Some(uuid) = &uuid {
+            if !data.workspaces.exists(uuid) {
+                return Err(Error::WorkspaceNotFound(uuid.clone()));
+            }
+        }
+        log::info!("Switched to workspace {:?}", uuid);
+        data.workspaces.active = uuid;
+
+        let mut commands = self.save_workspaces(&data.workspaces);
+        let hidden: Vec<String> = data
+            .profiles
+            .iter()
+            .filter(|p| !data.workspaces.shows(p))
+            .map(|p| p.uuid.clone())
+            .collect();
+        if current_profile.is_some_and(|current| hidden.iter().any(|uuid| uuid == current)) {
+            commands.push(ViewCommand::ShowWelcome);
+        }
+        // Perfis com a hibernação desligada continuam carregados (e notificando)
+        let hibernate: Vec<String> = data
+            .profiles
+            .iter()
+            .filter(|p| p.auto_hibernate && hidden.contains(&p.uuid))
+            .map(|p| p.uuid.clone())
+            .collect();
+        if data.settings.hibernate_inactive_workspaces && !hibernate.is_empty() {
+            commands.push(ViewCommand::HibernateProfiles(hibernate));
+        }
+        Ok(commands)
+    }
+
+    pub fn update_settings(&self, settings: AppSettings) -> Result<Vec<ViewCommand>, Error> {
+        let (mut commands, saved) = self.replace_settings(settings)?;
+        log::info!("Settings updated");
+
+        commands.push(ViewCommand::ShowWelcome);
+        commands.push(match saved {
+            Ok(()) => ViewCommand::ShowNotice("Configurações salvas".to_string()),
+            Err(e) => ViewCommand::ShowError(format!("Não foi possível salvar as configurações: {}", e)),
+        });
+        Ok(commands)
+    }
+
+    /// Configurações vindas de um backup (modo `Replace`). Reaplica atalhos e
+    /// log como o formulário, mas sem sair da página atual.
+    pub fn restore_settings(&self, settings: AppSettings) -> Result<Vec<ViewCommand>, Error> {
+        let (commands, saved) = self.replace_settings(settings)?;
+        saved?;
+        log::info!("Settings restored");
+        Ok(commands)
+    }
+
+    /// Troca as configurações e retorna o que precisa ser reaplicado nas views
+    /// junto com o resultado da gravação
+    fn replace_settings(&self, settings: AppSettings) -> Result<(Vec<ViewCommand>, Result<(), Error>), Error> {
+        let settings = settings.normalized()?;
+        let mut data = self.state.lock().unwrap();
+        let shortcuts_changed = data.settings.shortcuts != settings.shortcuts;
+        let hotkey_changed = data.settings.global_hotkey != settings.global_hotkey;
+        data.settings = settings;
+        let saved = self.storage.save_settings(&data.settings);
+        let verbose = data.settings.verbose_logging;
+        drop(data);
+
+        let mut commands = vec![ViewCommand::SetVerboseLogging(verbose)];
+        if shortcuts_changed {
+            commands.push(ViewCommand::RefreshShortcuts);
+        }
+        if hotkey_changed {
+            commands.push(ViewCommand::RegisterGlobalHotkey);
+        }
+        Ok((commands, saved))
+    }
+
+    /// Executa o atalho de teclado pressionado em qualquer webview. Os números
+    /// seguem a barra lateral; a navegação com Tab pula perfis desativados.
+    pub fn handle_shortcut(&self, combo: &str, current_profile: Option<&str>) -> Vec<ViewCommand> {
+        let data = self.state.lock().unwrap();
+        let Some(action) = data.settings.shortcuts.resolve(combo) else {
+            return Vec::new();
+        };
+        let activate = |profile: Option<&WebProfile>| -> Vec<ViewCommand> {
+            profile.map(|p| ViewCommand::ActivateProfile { uuid: p.uuid.clone() }).into_iter().collect()
+        };
+
+        let visible: Vec<&WebProfile> = data.profiles.iter().filter(|p| data.workspaces.shows(p)).collect();
+        match action {
+            ShortcutAction::ActivateProfile(index) => activate(visible.get(index).copied()),
+            ShortcutAction::NextProfile | ShortcutAction::PreviousProfile => {
+                let cycle: Vec<&WebProfile> = visible.into_iter().filter(|p| p.enabled).collect();
+                if cycle.is_empty() {
+                    return Vec::new();
+                }
+                let position = current_profile.and_then(|uuid| cycle.iter().position(|p| p.uuid == uuid));
+                let index = match (position, action == ShortcutAction::NextProfile) {
+                    (Some(i), true) => (i + 1) % cycle.len(),
+                    (Some(i), false) => (i + cycle.len() - 1) % cycle.len(),
+                    (None, true) => 0,
+                    (None, false) => cycle.len() - 1,
+                };
+                activate(Some(cycle[index]))
+            }
+            ShortcutAction::ReloadProfile => current_profile
+                .map(|uuid| ViewCommand::ReloadProfile { uuid: uuid.to_string() })
+                .into_iter()
+                .collect(),
+            ShortcutAction::OpenSettings => vec![ViewCommand::ShowSettings],
+            ShortcutAction::OpenPalette => vec![ViewCommand::ShowPalette],
+        }
+    }
+
+    /// Não lidas a partir do título da página (None se o perfil não existe). Os
+    /// padrões são compilados na primeira vez e depois só quando o perfil é editado.
+    pub fn unread_count(&self, uuid: &str, title: &str) -> Option<u32> {
+        let data = self.state.lock().unwrap();
+        let profile = data.profiles.iter().find(|p| p.uuid == uuid)?;
+        let mut cache = self.unread_patterns.lock().unwrap();
+        let patterns = cache.entry(uuid.to_string()).or_insert_with(|| {
+            // Só acontece com arquivos editados à mão; o formulário recusa padrões inválidos
+            crate::unread::compile_patterns(&profile.unread_patterns).unwrap_or_else(|e| {
+                log::warn!("Ignoring unread patterns of {}: {}", profile.name, e);
+                Vec::new()
+            })
+        });
+        Some(crate::unread::parse_unread_count(title, patterns))
+    }
+
+    /// Perfis para a paleta de comandos: os usados por último primeiro, depois os
+    /// que ainda não foram abertos, na ordem da barra lateral
+    pub fn palette_profiles(&self, last_used: &HashMap<String, Instant>) -> Vec<WebProfile> {
+        let mut profiles = self.state.lock().unwrap().profiles.clone();
+        // sort_by_key é estável: perfis sem uso mantêm a ordem original
+        profiles.sort_by_key(|p| std::cmp::Reverse(last_used.get(&p.uuid).copied()));
+        profiles
+    }
+
+    /// Esconder tem precedência sobre minimizar; sem nenhum dos dois, encerra
+    pub fn close_action(&self) -> CloseAction {
+        let data = self.state.lock().unwrap();
+        if data.settings.hide_on_close {
+            CloseAction::Hide
+        } else if data.settings.minimize_on_close {
+            CloseAction::Minimize
+        } else {
+            CloseAction::Exit
+        }
+    }
+
+    fn save_profiles(&self, profiles: &[WebProfile]) -> Vec<ViewCommand> {
+        match self.storage.save_profiles(profiles) {
+            Ok(()) => Vec::new(),
+            Err(e) => vec![ViewCommand::ShowError(format!("Não foi possível salvar os perfis: {}", e))],
+        }
+    }
+
+    fn save_workspaces(&self, workspaces: &Workspaces) -> Vec<ViewCommand> {
+        let mut commands = Vec::new();
+        if let Err(e) = self.storage.save_workspaces(workspaces) {
+            commands.push(ViewCommand::ShowError(format!("Não foi possível salvar os workspaces: {}", e)));
+        }
+        commands.push(ViewCommand::RefreshWorkspaces);
+        commands
+    }
+}
+
+fn workspace_name(name: &str) -> Result<String, Error> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(Error::Ipc("o nome do workspace não pode ficar vazio".to_string()));
+    }
+    Ok(name.to_string())
+}
+
+/// Remove espaços e entradas vazias de listas vindas do formulário
+fn trimmed(values: Vec<String>) -> Vec<String> {
+    values
+        .into_iter()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Arquivo que já está na pasta do perfil, como `profiles/{uuid}/icon.png`
+fn is_profile_file(uuid: &str, path: &str) -> bool {
+    path.starts_with(&format!("profiles/{}/", uuid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Armazenamento em memória que registra as chamadas
+    #[derive(Default)]
+    struct MemoryStorage {
+        log: Rc<RefCell<Vec<String>>>,
+        fail: bool,
+    }
+
+    impl MemoryStorage {
+        fn record(&self, call: String) -> Result<(), Error> {
+            self.log.borrow_mut().push(call);
+            if self.fail {
+                return Err(Error::Io(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "somente leitura")));
+            }
+            Ok(())
+        }
+    }
+
+    impl Storage for MemoryStorage {
+        fn save_profiles(&self, profiles: &[WebProfile]) -> Result<(), Error> {
+            self.record(format!("save_profiles {}", profiles.len()))
+        }
+
+        fn save_workspaces(&self, workspaces: &Workspaces) -> Result<(), Error> {
+            self.record(format!("save_workspaces {}", workspaces.workspaces.len()))
+        }
+
+        fn save_settings(&self, _settings: &AppSettings) -> Result<(), Error> {
+            self.record("save_settings".to_string())
+        }
+
+        fn save_profile_icon(&self, uuid: &str, source_path: &str) -> Result<String, Error> {
+            self.record(format!("save_profile_icon {}", source_path))?;
+            Ok(format!("profiles/{}/icon.png", uuid))
+        }
+
+        fn delete_profile_icon(&self, _uuid: &str) -> Result<(), Error> {
+            self.record("delete_profile_icon".to_string())
+        }
+
+        fn delete_profile_data(&self, _uuid: &str) -> Result<(), Error> {
+            self.record("delete_profile_data".to_string())
+        }
+    }
+
+    fn controller(fail: bool) -> (AppController, Rc<RefCell<Vec<String>>>) {
+        let storage = MemoryStorage { fail, ..Default::default() };
+        let log = storage.log.clone();
+        (AppController::new(crate::profile::create_app_state(), Box::new(storage)), log)
+    }
+
+    fn add(controller: &AppController, icon_path: Option<&str>) -> WebProfile {
+        controller.add_profile(
+            "Slack".to_string(),
+            "https://app.slack.com".to_string(),
+            icon_path.map(String::from),
+            Some("  ".to_string()),
+        );
+        controller.state.lock().unwrap().profiles.last().unwrap().clone()
+    }
+
+    fn update_for(profile: &WebProfile) -> ProfileUpdate {
+        ProfileUpdate {
+            name: profile.name.clone(),
+            url: profile.url.clone(),
+            icon_path: profile.icon_path.clone(),
+            user_agent: profile.user_agent.clone(),
+            auto_hibernate: profile.auto_hibernate,
+            unread_patterns: profile.unread_patterns.clone(),
+            notifications_muted: profile.notifications_muted,
+            allowed_domains: profile.allowed_domains.clone(),
+            enabled: profile.enabled,
+            workspaces: profile.workspaces.clone(),
+        }
+    }
+
+    #[test]
+    fn test_add_profile() {
+        let (controller, log) = controller(false);
+        let commands = controller.add_profile(
+            "Slack".to_string(),
+            "https://app.slack.com".to_string(),
+            None,
+            Some("  ".to_string()),
+        );
+        let profile = controller.state.lock().unwrap().profiles[0].clone();
+        assert_eq!(profile.user_agent, None);
+        assert_eq!(
+            commands,
+            vec![
+                ViewCommand::FetchFavicon { uuid: profile.uuid.clone(), url: profile.url.clone() },
+                ViewCommand::RefreshToolbar,
+                ViewCommand::ShowWelcome,
+            ]
+        );
+        assert_eq!(*log.borrow(), vec!["save_profiles 1"]);
+
+        let with_icon = add(&controller, Some("/home/user/slack.png"));
+        assert_eq!(with_icon.icon_path, Some(format!("profiles/{}/icon.png", with_icon.uuid)));
+        assert_eq!(log.borrow()[1], "save_profile_icon /home/user/slack.png");
+    }
+
+    #[test]
+    fn test_update_profile() {
+        let (controller, log) = controller(false);
+        let profile = add(&controller, Some("/tmp/icon.png"));
+        assert_eq!(controller.unread_count(&profile.uuid, "[4] Slack"), Some(4));
+        assert_eq!(controller.unread_count("desconhecido", "(4) Slack"), None);
+
+        // Mesmo User-Agent, URL e domínios: a webview é mantida
+        let mut changes = update_for(&profile);
+        changes.name = "Slack (trabalho)".to_string();
+        changes.unread_patterns = vec![" \\((\\d+)\\) ".to_string(), "  ".to_string()];
+        let commands = controller.update_profile(&profile.uuid, changes).unwrap();
+        assert_eq!(commands, vec![ViewCommand::RefreshToolbar, ViewCommand::ShowWelcome]);
+        let updated = controller.state.lock().unwrap().profiles[0].clone();
+        assert_eq!(updated.name, "Slack (trabalho)");
+        assert_eq!(updated.unread_patterns, vec!["\\((\\d+)\\)"]);
+        // Os padrões compilados acompanham a edição
+        assert_eq!(controller.unread_count(&profile.uuid, "[4] Slack"), Some(0));
+        assert_eq!(controller.unread_count(&profile.uuid, "Slack (6)"), Some(6));
+
+        // Padrão inválido: nada é alterado
+        let mut invalid = update_for(&updated);
+        invalid.name = "Outro nome".to_string();
+        invalid.unread_patterns = vec!["(\\d+".to_string()];
+        assert!(matches!(controller.update_profile(&profile.uuid, invalid), Err(Error::Config(_))));
+        assert_eq!(controller.state.lock().unwrap().profiles[0].name, "Slack (trabalho)");
+
+        // Remover o ícone apaga o arquivo e busca o favicon; User-Agent novo recria a webview
+        let mut changes = update_for(&updated);
+        changes.icon_path = None;
+        changes.user_agent = Some("Mozilla/5.0".to_string());
+        let commands = controller.update_profile(&profile.uuid, changes).unwrap();
+        assert!(commands.contains(&ViewCommand::FetchFavicon { uuid: profile.uuid.clone(), url: profile.url.clone() }));
+        assert!(commands.contains(&ViewCommand::RebuildProfileView { uuid: profile.uuid.clone() }));
+        assert!(log.borrow().contains(&"delete_profile_icon".to_string()));
+
+        // Desativar descarta a webview
+        let mut changes = update_for(&controller.state.lock().unwrap().profiles[0]);
+        changes.enabled = false;
+        let commands = controller.update_profile(&profile.uuid, changes).unwrap();
+        assert_eq!(commands[0], ViewCommand::RebuildProfileView { uuid: profile.uuid.clone() });
+
+        let missing = controller.update_profile("missing", update_for(&profile));
+        assert!(matches!(missing, Err(Error::ProfileNotFound(uuid)) if uuid == "missing"));
+    }
+
+    #[test]
+    fn test_remove_profile_and_favicon() {
+        let (controller, log) = controller(false);
+        let profile = add(&controller, None);
+
+        let commands = controller.refresh_profile_icon(&profile.uuid).unwrap();
+        assert_eq!(commands, vec![ViewCommand::FetchFavicon { uuid: profile.uuid.clone(), url: profile.url.clone() }]);
+
+        let icon = format!("profiles/{}/favicon.png", profile.uuid);
+        assert_eq!(controller.apply_favicon(&profile.uuid, icon.clone()), vec![ViewCommand::RefreshToolbar]);
+        assert_eq!(controller.state.lock().unwrap().profiles[0].icon_path, Some(icon.clone()));
+
+        let commands = controller.remove_profile(&profile.uuid).unwrap();
+        assert_eq!(commands[0], ViewCommand::ForgetProfile { uuid: profile.uuid.clone() });
+        assert!(controller.state.lock().unwrap().profiles.is_empty());
+        assert_eq!(log.borrow().last().unwrap(), "delete_profile_data");
+
+        // Favicon que chega depois da remoção é descartado
+        assert!(controller.apply_favicon(&profile.uuid, icon).is_empty());
+        assert!(matches!(controller.remove_profile(&profile.uuid), Err(Error::ProfileNotFound(_))));
+        assert!(matches!(controller.refresh_profile_icon(&profile.uuid), Err(Error::ProfileNotFound(_))));
+    }
+
+    #[test]
+    fn test_reorder_profiles() {
+        let (controller, log) = controller(false);
+        let gmail = add(&controller, None);
+        let slack = add(&controller, None);
+        let notion = add(&controller, None);
+        let order = || -> Vec<String> { controller.state.lock().unwrap().profiles.iter().map(|p| p.uuid.clone()).collect() };
+        log.borrow_mut().clear();
+
+        let commands = controller.reorder_profiles(&[notion.uuid.clone(), gmail.uuid.clone(), slack.uuid.clone()]);
+        assert_eq!(commands, vec![ViewCommand::RefreshToolbar]);
+        assert_eq!(order(), vec![notion.uuid.clone(), gmail.uuid.clone(), slack.uuid.clone()]);
+        assert_eq!(*log.borrow(), vec!["save_profiles 3"]);
+
+        // Só os perfis visíveis trocam de lugar; UUIDs desconhecidos são ignorados
+        controller.reorder_profiles(&[slack.uuid.clone(), "missing".to_string(), notion.uuid.clone()]);
+        assert_eq!(order(), vec![slack.uuid.clone(), gmail.uuid.clone(), notion.uuid.clone()]);
+
+        // Sem mudança de ordem não há gravação
+        log.borrow_mut().clear();
+        assert!(controller.reorder_profiles(&[slack.uuid.clone(), gmail.uuid.clone()]).is_empty());
+        assert!(log.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_shortcuts() {
+        let (controller, _) = controller(false);
+        let gmail = add(&controller, None);
+        let slack = add(&controller, None);
+        let notion = add(&controller, None);
+        let activate = |profile: &WebProfile| vec![ViewCommand::ActivateProfile { uuid: profile.uuid.clone() }];
+
+        assert_eq!(controller.handle_shortcut("Ctrl+2", None), activate(&slack));
+        assert!(controller.handle_shortcut("Ctrl+4", None).is_empty());
+        assert_eq!(controller.handle_shortcut("Ctrl+Tab", None), activate(&gmail));
+        assert_eq!(controller.handle_shortcut("Ctrl+Tab", Some(&notion.uuid)), activate(&gmail));
+        assert_eq!(controller.handle_shortcut("Ctrl+Shift+Tab", Some(&gmail.uuid)), activate(&notion));
+        assert_eq!(
+            controller.handle_shortcut("Ctrl+R", Some(&slack.uuid)),
+            vec![ViewCommand::ReloadProfile { uuid: slack.uuid.clone() }]
+        );
+        assert!(controller.handle_shortcut("Ctrl+R", None).is_empty());
+        assert_eq!(controller.handle_shortcut("Ctrl+,", None), vec![ViewCommand::ShowSettings]);
+        assert_eq!(controller.handle_shortcut("Ctrl+K", Some(&gmail.uuid)), vec![ViewCommand::ShowPalette]);
+        assert!(controller.handle_shortcut("Ctrl+Q", None).is_empty());
+
+        // Perfis desativados ficam fora do ciclo, mas mantêm o número
+        controller.state.lock().unwrap().profiles[1].enabled = false;
+        assert_eq!(controller.handle_shortcut("Ctrl+Tab", Some(&gmail.uuid)), activate(&notion));
+        assert_eq!(controller.handle_shortcut("Ctrl+2", None), activate(&slack));
+
+        // Os números seguem o workspace selecionado
+        controller.add_workspace("Trabalho").unwrap();
+        let work = controller.state.lock().unwrap().workspaces.workspaces[0].uuid.clone();
+        controller.state.lock().unwrap().profiles[2].workspaces = vec![work.clone()];
+        controller.switch_workspace(Some(work), None).unwrap();
+        assert_eq!(controller.handle_shortcut("Ctrl+1", None), activate(&notion));
+        assert!(controller.handle_shortcut("Ctrl+2", None).is_empty());
+    }
+
+    #[test]
+    fn test_palette_profiles() {
+        let (controller, _) = controller(false);
+        let gmail = add(&controller, None);
+        let slack = add(&controller, None);
+        let notion = add(&controller, None);
+        let calendar = add(&controller, None);
+
+        let now = Instant::now();
+        let last_used = HashMap::from([
+            (slack.uuid.clone(), now),
+            (calendar.uuid.clone(), now + std::time::Duration::from_secs(5)),
+        ]);
+        let order: Vec<String> = controller.palette_profiles(&last_used).into_iter().map(|p| p.uuid).collect();
+        assert_eq!(order, vec![calendar.uuid, slack.uuid, gmail.uuid, notion.uuid]);
+    }
+
+    #[test]
+    fn test_settings_and_close_action() {
+        let (controller, _) = controller(false);
+        assert_eq!(controller.close_action(), CloseAction::Hide);
+
+        let settings = AppSettings {
+            hide_on_close: false,
+            minimize_on_close: true,
+            verbose_logging: true,
+            download_dir: Some("  ".to_string()),
+            ..AppSettings::default()
+        };
+        let commands = controller.update_settings(settings).unwrap();
+        assert_eq!(
+            commands,
+            vec![
+                ViewCommand::SetVerboseLogging(true),
+                ViewCommand::ShowWelcome,
+                ViewCommand::ShowNotice("Configurações salvas".to_string()),
+            ]
+        );
+        assert_eq!(controller.state.lock().unwrap().settings.download_dir, None);
+
+        // Atalhos inválidos recusam o formulário inteiro
+        let mut settings = controller.state.lock().unwrap().settings.clone();
+        settings.shortcuts.reload_profile = "R".to_string();
+        assert!(matches!(controller.update_settings(settings.clone()), Err(Error::Config(_))));
+        assert_eq!(controller.state.lock().unwrap().settings.shortcuts.reload_profile, "Ctrl+R");
+        settings.shortcuts.reload_profile = "f5".to_string();
+        assert!(controller.update_settings(settings.clone()).unwrap().contains(&ViewCommand::RefreshShortcuts));
+        assert_eq!(controller.state.lock().unwrap().settings.shortcuts.reload_profile, "F5");
+
+        settings.global_hotkey = "Banana".to_string();
+        assert!(matches!(controller.update_settings(settings.clone()), Err(Error::Config(_))));
+        settings.global_hotkey = "ctrl+alt+f".to_string();
+        let commands = controller.update_settings(settings.clone()).unwrap();
+        assert!(commands.contains(&ViewCommand::RegisterGlobalHotkey));
+        assert!(!commands.contains(&ViewCommand::RefreshShortcuts));
+        assert_eq!(controller.state.lock().unwrap().settings.global_hotkey, "Ctrl+Alt+F");
+        assert_eq!(controller.close_action(), CloseAction::Minimize);
+
+        controller.state.lock().unwrap().settings.minimize_on_close = false;
+        assert_eq!(controller.close_action(), CloseAction::Exit);
+
+        // Backup restaurado: reaplica atalhos sem sair da página atual
+        let commands = controller.restore_settings(AppSettings::default()).unwrap();
+        assert_eq!(
+            commands,
+            vec![
+                ViewCommand::SetVerboseLogging(false),
+                ViewCommand::RefreshShortcuts,
+                ViewCommand::RegisterGlobalHotkey,
+            ]
+        );
+        assert_eq!(controller.state.lock().unwrap().settings.global_hotkey, "");
+    }
+
+    #[test]
+    fn test_workspaces() {
+        let (controller, log) = controller(false);
+        let personal = add(&controller, None);
+        assert_eq!(controller.add_workspace("  Trabalho ").unwrap(), vec![ViewCommand::RefreshWorkspaces]);
+        assert!(matches!(controller.add_workspace(" "), Err(Error::Ipc(_))));
+        let work = controller.state.lock().unwrap().workspaces.workspaces[0].clone();
+        assert_eq!(work.name, "Trabalho");
+
+        // Perfis novos entram no workspace selecionado
+        controller.switch_workspace(Some(work.uuid.clone()), None).unwrap();
+        let slack = add(&controller, None);
+        assert_eq!(slack.workspaces, vec![work.uuid.clone()]);
+        assert!(personal.workspaces.is_empty());
+
+        // UUIDs desconhecidos enviados pelo formulário são descartados
+        let mut changes = update_for(&personal);
+        changes.workspaces = vec![work.uuid.clone(), "missing".to_string()];
+        controller.update_profile(&personal.uuid, changes).unwrap();
+        assert_eq!(controller.state.lock().unwrap().profiles[0].workspaces, vec![work.uuid.clone()]);
+
+        // O perfil aberto fica de fora: volta para a tela inicial e hiberna os demais
+        let other = controller.add_workspace("Cliente X").unwrap();
+        assert_eq!(other, vec![ViewCommand::RefreshWorkspaces]);
+        let client = controller.state.lock().unwrap().workspaces.workspaces[1].uuid.clone();
+        controller.state.lock().unwrap().settings.hibernate_inactive_workspaces = true;
+        let commands = controller.switch_workspace(Some(client.clone()), Some(&slack.uuid)).unwrap();
+        assert_eq!(
+            commands,
+            vec![
+                ViewCommand::RefreshWorkspaces,
+                ViewCommand::ShowWelcome,
+                ViewCommand::HibernateProfiles(vec![personal.uuid.clone(), slack.uuid.clone()]),
+            ]
+        );
+
+        // Perfis com a hibernação desligada não são hibernados, mas o aberto ainda sai da tela
+        controller.switch_workspace(None, None).unwrap();
+        controller.state.lock().unwrap().profiles[1].auto_hibernate = false;
+        let commands = controller.switch_workspace(Some(client.clone()), Some(&slack.uuid)).unwrap();
+        assert_eq!(
+            commands,
+            vec![
+                ViewCommand::RefreshWorkspaces,
+                ViewCommand::ShowWelcome,
+                ViewCommand::HibernateProfiles(vec![personal.uuid.clone()]),
+            ]
+        );
+        assert_eq!(controller.switch_workspace(None, Some(&slack.uuid)).unwrap(), vec![ViewCommand::RefreshWorkspaces]);
+        assert!(matches!(controller.switch_workspace(Some("missing".to_string()), None), Err(Error::WorkspaceNotFound(_))));
+
+        controller.rename_workspace(&client, "Cliente Y").unwrap();
+        assert_eq!(controller.state.lock().unwrap().workspaces.workspaces[1].name, "Cliente Y");
+
+        // Remover o workspace ativo volta a exibir todos e limpa as associações
+        controller.switch_workspace(Some(work.uuid.clone()), None).unwrap();
+        log.borrow_mut().clear();
+        controller.remove_workspace(&work.uuid).unwrap();
+        let data = controller.state.lock().unwrap();
+        assert_eq!(data.workspaces.active, None);
+        assert!(data.profiles.iter().all(|p| p.workspaces.is_empty()));
+        assert_eq!(*log.borrow(), vec!["save_profiles 2", "save_workspaces 1"]);
+        drop(data);
+        assert!(matches!(controller.remove_workspace(&work.uuid), Err(Error::WorkspaceNotFound(_))));
+    }
+
+    #[test]
+    fn test_storage_failures_are_reported() {
+        let (controller, _) = controller(true);
+        let commands = controller.add_profile("Gmail".to_string(), "https://mail.google.com".to_string(), None, None);
+        assert!(matches!(&commands[1], ViewCommand::ShowError(message) if message.contains("salvar os perfis")));
+        // O perfil continua na memória mesmo sem conseguir gravar
+        assert_eq!(controller.state.lock().unwrap().profiles.len(), 1);
+
+        let commands = controller.update_settings(AppSettings::default()).unwrap();
+        assert!(matches!(commands.last(), Some(ViewCommand::ShowError(_))));
+    }
+}
@@ -0,0 +1,223 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::profile::AppSettings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum DownloadStatus {
+    InProgress,
+    Completed,
+    Failed,
+}
+
+/// Um download iniciado por uma webview de perfil
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadEntry {
+    pub id: u64,
+    pub profile_uuid: String,
+    pub profile_name: String,
+    pub url: String,
+    pub path: String,
+    pub file_name: String,
+    pub status: DownloadStatus,
+    /// Bytes já gravados em disco (tamanho final quando concluído)
+    pub bytes: u64,
+    /// Início do download em segundos desde a época Unix
+    pub started_at: u64,
+}
+
+/// Histórico dos downloads da sessão atual
+#[derive(Debug, Default)]
+pub struct DownloadManager {
+    entries: Vec<DownloadEntry>,
+    next_id: u64,
+}
+
+impl DownloadManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start(&mut self, profile_uuid: &str, profile_name: &str, url: &str, path: &Path) -> u64 {
+        self.next_id += 1;
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        self.entries.push(DownloadEntry {
+            id: self.next_id,
+            profile_uuid: profile_uuid.to_string(),
+            profile_name: profile_name.to_string(),
+            url: url.to_string(),
+            path: path.to_string_lossy().to_string(),
+            file_name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| url.to_string()),
+            status: DownloadStatus::InProgress,
+            bytes: 0,
+            started_at,
+        });
+        self.next_id
+    }
+
+    /// Finaliza o download mais antigo em andamento do perfil para esta URL.
+    ///
+    /// Retorna `None` quando não há download correspondente, o que acontece se
+    /// o WebContext do perfil tiver recebido os handlers mais de uma vez
+    /// (a webview é recriada ao sair da hibernação).
+    pub fn finish(&mut self, profile_uuid: &str, url: &str, path: Option<PathBuf>, success: bool) -> Option<&DownloadEntry> {
+        let entry = self.entries.iter_mut().find(|e| {
+            e.status == DownloadStatus::InProgress && e.profile_uuid == profile_uuid && e.url == url
+        })?;
+
+        if let Some(path) = path {
+            entry.path = path.to_string_lossy().to_string();
+        }
+        entry.status = if success { DownloadStatus::Completed } else { DownloadStatus::Failed };
+        entry.bytes = file_size(&entry.path);
+        Some(entry)
+    }
+
+    /// Downloads do mais recente para o mais antigo, com o progresso atualizado
+    pub fn entries(&self) -> Vec<DownloadEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .map(|entry| {
+                let mut entry = entry.clone();
+                if entry.status == DownloadStatus::InProgress {
+                    entry.bytes = file_size(&entry.path);
+                }
+                entry
+            })
+            .collect()
+    }
+
+    pub fn get(&self, id: u64) -> Option<&DownloadEntry> {
+        self.entries.iter().find(|e| e.id == id)
+    }
+
+    pub fn has_active(&self) -> bool {
+        self.entries.iter().any(|e| e.status == DownloadStatus::InProgress)
+    }
+
+    /// Remove da lista os downloads que não estão mais em andamento
+    pub fn clear_finished(&mut self) {
+        self.entries.retain(|e| e.status == DownloadStatus::InProgress);
+    }
+}
+
+fn file_size(path: &str) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// Pasta de downloads configurada, ou a pasta padrão do sistema
+pub fn download_directory(settings: &AppSettings) -> PathBuf {
+    settings
+        .download_dir
+        .as_ref()
+        .map(PathBuf::from)
+        .or_else(dirs::download_dir)
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Caminho final de um download: dentro de `dir` (ou da subpasta do perfil),
+/// sem sobrescrever arquivos existentes
+pub fn resolve_destination(dir: &Path, profile_folder: Option<&str>, file_name: &str) -> PathBuf {
+    let mut path = dir.to_path_buf();
+    if let Some(folder) = profile_folder.map(sanitize_file_name).filter(|f| !f.is_empty()) {
+        path.push(folder);
+    }
+
+    let file_name = sanitize_file_name(file_name);
+    path.push(if file_name.is_empty() { "download".to_string() } else { file_name });
+    unique_path(path)
+}
+
+/// Acrescenta " (1)", " (2)"... ao nome enquanto o arquivo já existir
+pub fn unique_path(path: PathBuf) -> PathBuf {
+    if !path.exists() {
+        return path;
+    }
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    // Extensão a partir do primeiro ponto, para manter ".tar.gz" junto
+    let (stem, ext) = match file_name.find('.') {
+        Some(index) if index > 0 => file_name.split_at(index),
+        _ => (file_name.as_str(), ""),
+    };
+
+    let mut counter = 1;
+    loop {
+        let candidate = path.with_file_name(format!("{} ({}){}", stem, counter, ext));
+        if !candidate.exists() {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect::<String>()
+        .trim()
+        .trim_matches('.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_lifecycle() {
+        let mut manager = DownloadManager::new();
+        let id = manager.start("p1", "Gmail", "https://mail.example.com/a.pdf", Path::new("/tmp/none/a.pdf"));
+        assert!(manager.has_active());
+        assert_eq!(manager.get(id).unwrap().file_name, "a.pdf");
+
+        // Outro perfil com a mesma URL não finaliza este download
+        assert!(manager.finish("p2", "https://mail.example.com/a.pdf", None, true).is_none());
+
+        let entry = manager.finish("p1", "https://mail.example.com/a.pdf", None, true).unwrap();
+        assert_eq!(entry.status, DownloadStatus::Completed);
+        assert_eq!(entry.path, "/tmp/none/a.pdf");
+        assert!(!manager.has_active());
+
+        // Notificação duplicada do mesmo download é ignorada
+        assert!(manager.finish("p1", "https://mail.example.com/a.pdf", None, true).is_none());
+
+        manager.clear_finished();
+        assert!(manager.entries().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_destination() {
+        let dir = std::env::temp_dir().join(format!("feather-alloy-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let first = resolve_destination(&dir, None, "report.tar.gz");
+        assert_eq!(first, dir.join("report.tar.gz"));
+        std::fs::write(&first, b"x").unwrap();
+        assert_eq!(resolve_destination(&dir, None, "report.tar.gz"), dir.join("report (1).tar.gz"));
+
+        assert_eq!(
+            resolve_destination(&dir, Some("Slack: Trabalho"), "../notes.txt"),
+            dir.join("Slack_ Trabalho").join("_notes.txt")
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
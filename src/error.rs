@@ -0,0 +1,142 @@
+use std::fmt;
+
+/// Erros da biblioteca. As mensagens (`Display`) são exibidas ao usuário;
+/// quem precisar tratar um caso específico pode casar com a variante.
+#[derive(Debug)]
+pub enum Error {
+    /// Falha de leitura ou escrita em disco
+    Io(std::io::Error),
+    /// JSON inválido em arquivos de configuração ou mensagens
+    Json(serde_json::Error),
+    /// Falha de rede ou resposta HTTP inesperada
+    Network(String),
+    /// Falha da interface: janela, webviews, menus, bandeja ou atalho global
+    WebView(String),
+    /// O servidor de notificações do sistema recusou ou não respondeu
+    Notification(String),
+    /// Nenhum perfil com o UUID informado
+    ProfileNotFound(String),
+    /// Nenhum workspace com o UUID informado
+    WorkspaceNotFound(String),
+    /// URL malformada ou sem esquema suportado
+    InvalidUrl(String),
+    /// Arquivo de configuração em formato ou versão não suportada
+    Config(String),
+    /// Arquivo de backup inválido ou incompleto
+    Archive(String),
+    /// Falha ao ler o banco de dados de outra aplicação (importação do Ferdium)
+    Database(rusqlite::Error),
+    /// Requisição IPC recusada ou sem tratamento
+    Ipc(String),
+    /// O sistema não informou um diretório de dados para a aplicação
+    NoDataDir,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "Erro de arquivo: {}", e),
+            Error::Json(e) => write!(f, "JSON inválido: {}", e),
+            Error::Network(message) => write!(f, "Erro de rede: {}", message),
+            Error::WebView(message) => write!(f, "Erro na interface: {}", message),
+            Error::Notification(message) => write!(f, "Erro ao exibir notificação: {}", message),
+            Error::ProfileNotFound(uuid) => write!(f, "Perfil não encontrado: {}", uuid),
+            Error::WorkspaceNotFound(uuid) => write!(f, "Workspace não encontrado: {}", uuid),
+            Error::InvalidUrl(url) => write!(f, "URL inválida: {}", url),
+            Error::Config(message) => write!(f, "Configuração inválida: {}", message),
+            Error::Archive(message) => write!(f, "Backup inválido: {}", message),
+            Error::Database(e) => write!(f, "Erro no banco de dados: {}", e),
+            Error::Ipc(message) => write!(f, "Requisição inválida: {}", message),
+            Error::NoDataDir => write!(f, "Diretório de dados não encontrado"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Json(e) => Some(e),
+            Error::Database(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Error::Database(e)
+    }
+}
+
+impl From<zip::result::ZipError> for Error {
+    fn from(e: zip::result::ZipError) -> Self {
+        match e {
+            zip::result::ZipError::Io(e) => Error::Io(e),
+            e => Error::Archive(e.to_string()),
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Network(e.to_string())
+    }
+}
+
+impl From<url::ParseError> for Error {
+    fn from(e: url::ParseError) -> Self {
+        Error::InvalidUrl(e.to_string())
+    }
+}
+
+impl From<wry::Error> for Error {
+    fn from(e: wry::Error) -> Self {
+        Error::WebView(e.to_string())
+    }
+}
+
+impl From<tao::error::OsError> for Error {
+    fn from(e: tao::error::OsError) -> Self {
+        Error::WebView(e.to_string())
+    }
+}
+
+impl From<muda::Error> for Error {
+    fn from(e: muda::Error) -> Self {
+        Error::WebView(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions_and_messages() {
+        let io: Error = std::io::Error::new(std::io::ErrorKind::NotFound, "profiles.json").into();
+        assert!(matches!(io, Error::Io(ref e) if e.kind() == std::io::ErrorKind::NotFound));
+        assert!(std::error::Error::source(&io).is_some());
+
+        let json: Error = serde_json::from_str::<u32>("{").unwrap_err().into();
+        assert!(matches!(json, Error::Json(_)));
+
+        let url: Error = url::Url::parse("not a url").unwrap_err().into();
+        assert!(matches!(url, Error::InvalidUrl(_)));
+
+        let missing = Error::ProfileNotFound("abc".to_string());
+        assert_eq!(missing.to_string(), "Perfil não encontrado: abc");
+    }
+}
@@ -1,16 +1,18 @@
 use reqwest::blocking::Client;
 use scraper::{Html, Selector};
 
+use crate::Error;
+
 pub struct FaviconResult {
     pub bytes: Vec<u8>,
     pub extension: String,
 }
 
-pub fn fetch_favicon(target_url: &str) -> Result<FaviconResult, Box<dyn std::error::Error>> {
+pub fn fetch_favicon(target_url: &str) -> Result<FaviconResult, Error> {
     match attempt_fetch(target_url) {
         Ok(res) => Ok(res),
         Err(e) => {
-            println!("[Favicon] Primary fetch failed for {}: {}", target_url, e);
+            log::debug!("Primary fetch failed for {}: {}", target_url, e);
             
             if let Ok(url_obj) = url::Url::parse(target_url) {
                 if let Some(host) = url_obj.domain() {
@@ -18,7 +20,7 @@ pub fn fetch_favicon(target_url: &str) -> Result<FaviconResult, Box<dyn std::err
                      // Try root domain first
                      if parts.len() >= 3 {
                          let root_domain = format!("https://{}.{}", parts[parts.len()-2], parts[parts.len()-1]);
-                         println!("[Favicon] Trying fallback to root domain: {}", root_domain);
+                         log::debug!("Trying fallback to root domain: {}", root_domain);
                          if let Ok(res) = attempt_fetch(&root_domain) {
                              return Ok(res);
                          }
@@ -26,7 +28,7 @@ pub fn fetch_favicon(target_url: &str) -> Result<FaviconResult, Box<dyn std::err
                      
                      // Final fallback: Google Favicon Service
                      let google_url = format!("https://www.google.com/s2/favicons?domain={}&sz=128", host);
-                     println!("[Favicon] Trying Google Favicon Service: {}", google_url);
+                     log::debug!("Trying Google Favicon Service: {}", google_url);
                      let client = Client::builder()
                         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/121.0.0.0 Safari/537.36")
                         .build()?;
@@ -44,7 +46,7 @@ pub fn fetch_favicon(target_url: &str) -> Result<FaviconResult, Box<dyn std::err
 }
 
 // Helper to attempt fetch
-fn attempt_fetch(url: &str) -> Result<FaviconResult, Box<dyn std::error::Error>> {
+fn attempt_fetch(url: &str) -> Result<FaviconResult, Error> {
     let client = Client::builder()
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/121.0.0.0 Safari/537.36")
         .build()?;
@@ -59,13 +61,13 @@ fn attempt_fetch(url: &str) -> Result<FaviconResult, Box<dyn std::error::Error>>
         .send()?;
 
     if !response.status().is_success() {
-            println!("[Favicon] HTML fetch failed for {}: {}. Trying direct /favicon.ico", url, response.status());
+            log::debug!("HTML fetch failed for {}: {}. Trying direct /favicon.ico", url, response.status());
             // Fallback to trying /favicon.ico directly
             let base_url = response.url().clone();
             if let Ok(icon_url) = base_url.join("/favicon.ico") {
                 return download_icon(&client, icon_url);
             }
-            return Err(format!("Status: {}", response.status()).into());
+            return Err(Error::Network(format!("HTTP {} em {}", response.status(), url)));
     }
 
     let base_url = response.url().clone();
@@ -74,7 +76,7 @@ fn attempt_fetch(url: &str) -> Result<FaviconResult, Box<dyn std::error::Error>>
     parse_favicon(&html_content, &base_url, &client)
 }
 
-fn parse_favicon(html_content: &str, base_url: &reqwest::Url, client: &Client) -> Result<FaviconResult, Box<dyn std::error::Error>> {
+fn parse_favicon(html_content: &str, base_url: &reqwest::Url, client: &Client) -> Result<FaviconResult, Error> {
     let document = Html::parse_document(html_content);
     // Select all links that might be icons
     let link_selector = Selector::parse("link").unwrap();
@@ -119,21 +121,21 @@ fn parse_favicon(html_content: &str, base_url: &reqwest::Url, client: &Client) -
             if !best_href.is_empty() {
                  let root_favicon = base_url.join("/favicon.ico")?;
                  if root_favicon != icon_url {
-                     println!("[Favicon] Failed, trying root: {}", root_favicon);
+                     log::debug!("Failed, trying root: {}", root_favicon);
                      return download_icon(client, root_favicon);
                  }
             }
-            Err("Failed to download favicon".into())
+            Err(Error::Network(format!("falha ao baixar o ícone de {}", base_url)))
         }
     }
 }
 
-fn download_icon(client: &Client, icon_url: reqwest::Url) -> Result<FaviconResult, Box<dyn std::error::Error>> {
-    println!("[Favicon] Downloading icon from: {}", icon_url);
+fn download_icon(client: &Client, icon_url: reqwest::Url) -> Result<FaviconResult, Error> {
+    log::debug!("Downloading icon from: {}", icon_url);
     let icon_response = client.get(icon_url.clone()).send()?;
     
     if !icon_response.status().is_success() {
-        return Err(format!("Icon fetch failed: {}", icon_response.status()).into());
+        return Err(Error::Network(format!("HTTP {} em {}", icon_response.status(), icon_url)));
     }
 
     // Extract content-type before consuming body
@@ -0,0 +1,373 @@
+use rusqlite::{types::ValueRef, Connection, OpenFlags};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::profile::WebProfile;
+use crate::Error;
+
+// Segundos entre 1601-01-01 (época dos timestamps do Chromium) e 1970-01-01
+const CHROMIUM_EPOCH_OFFSET_SECS: i64 = 11_644_473_600;
+
+/// Pasta de dados do Ferdium (ou dos antecessores Ferdi/Franz) com banco local de serviços
+pub fn default_data_dir() -> Option<PathBuf> {
+    let config_dir = dirs::config_dir()?;
+    ["Ferdium", "Ferdi", "Franz"]
+        .iter()
+        .map(|name| config_dir.join(name))
+        .find(|dir| dir.join("server.sqlite").is_file())
+}
+
+/// Resultado da importação do Ferdium
+#[derive(Debug, Default)]
+pub struct FerdiumImport {
+    /// Perfis criados (ícones e cookies já copiados para `profiles/{uuid}/`)
+    pub profiles: Vec<WebProfile>,
+    /// Serviços ignorados por não ter uma URL resolvível
+    pub skipped: Vec<String>,
+    /// Quantos perfis tiveram cookies de login copiados
+    pub sessions_copied: usize,
+}
+
+/// Serviço como gravado na tabela `services` do Ferdium
+#[derive(Debug, Clone)]
+struct FerdiumService {
+    service_id: String,
+    name: String,
+    recipe_id: String,
+    settings: Value,
+}
+
+/// Cookie da tabela `cookies` de uma partição Chromium
+#[derive(Debug, Clone)]
+struct ChromiumCookie {
+    host: String,
+    name: String,
+    value: String,
+    path: String,
+    expires_utc: i64,
+    secure: bool,
+    http_only: bool,
+}
+
+/// Lê os serviços de uma instalação do Ferdium e cria um perfil para cada um.
+///
+/// Com `copy_sessions`, os cookies persistentes da partição de cada serviço
+/// são convertidos para o formato do WebContext. Cookies criptografados e o
+/// armazenamento LevelDB do Chromium (localStorage, IndexedDB) não têm
+/// equivalente no WebKit, então alguns serviços ainda pedirão login.
+pub fn import_services(
+    ferdium_dir: &Path,
+    data_dir: &Path,
+    copy_sessions: bool,
+) -> Result<FerdiumImport, Error> {
+    let services = read_services(&ferdium_dir.join("server.sqlite"))?;
+    let mut result = FerdiumImport::default();
+
+    for service in services {
+        let recipe = read_recipe(ferdium_dir, &service.recipe_id);
+        let Some(url) = service_url(&service.settings, recipe.as_ref()) else {
+            log::warn!("Skipping service without URL: {} ({})", service.name, service.recipe_id);
+            result.skipped.push(service.name);
+            continue;
+        };
+
+        let mut profile = WebProfile::new(service.name.clone(), url, None, None);
+        profile.enabled = bool_setting(&service.settings, "isEnabled", true);
+        profile.notifications_muted = bool_setting(&service.settings, "isMuted", false)
+            || !bool_setting(&service.settings, "isNotificationEnabled", true);
+
+        let profile_dir = data_dir.join("profiles").join(&profile.uuid);
+        fs::create_dir_all(&profile_dir)?;
+
+        if let Some(icon) = find_icon(ferdium_dir, &service) {
+            let extension = icon.extension().and_then(|e| e.to_str()).unwrap_or("png").to_lowercase();
+            let file_name = format!("icon.{}", extension);
+            fs::copy(&icon, profile_dir.join(&file_name))?;
+            profile.icon_path = Some(format!("profiles/{}/{}", profile.uuid, file_name));
+        }
+
+        if copy_sessions && is_safe_name(&service.service_id) {
+            match copy_cookies(ferdium_dir, &service.service_id, &profile_dir) {
+                Ok(0) => {}
+                Ok(count) => {
+                    log::info!("Copied {} cookies for {}", count, profile.name);
+                    result.sessions_copied += 1;
+                }
+                Err(e) => log::warn!("Failed to copy session of {}: {}", profile.name, e),
+            }
+        }
+
+        result.profiles.push(profile);
+    }
+
+    log::info!(
+        "Imported {} services from {:?} ({} skipped)",
+        result.profiles.len(), ferdium_dir, result.skipped.len()
+    );
+    Ok(result)
+}
+
+fn read_services(db_path: &Path) -> Result<Vec<FerdiumService>, Error> {
+    if !db_path.is_file() {
+        return Err(Error::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("banco de serviços do Ferdium não encontrado: {}", db_path.display()),
+        )));
+    }
+
+    let connection = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut statement = connection.prepare("SELECT * FROM services")?;
+    // Os nomes das colunas mudaram entre versões (camelCase/snake_case)
+    let columns: Vec<String> = statement.column_names().into_iter().map(String::from).collect();
+
+    let rows = statement.query_map([], |row| {
+        let mut values = HashMap::new();
+        for (index, column) in columns.iter().enumerate() {
+            let value = match row.get_ref(index)? {
+                ValueRef::Text(text) => String::from_utf8_lossy(text).to_string(),
+                ValueRef::Integer(number) => number.to_string(),
+                _ => continue,
+            };
+            values.insert(column.clone(), value);
+        }
+        Ok(values)
+    })?;
+
+    let mut services = Vec::new();
+    for row in rows {
+        services.push(service_from_columns(&row?));
+    }
+    // Manter a ordem da barra lateral do Ferdium
+    services.sort_by_key(|s| s.settings.get("order").and_then(Value::as_i64).unwrap_or(i64::MAX));
+    Ok(services)
+}
+
+fn service_from_columns(columns: &HashMap<String, String>) -> FerdiumService {
+    let column = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| columns.get(*name))
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    FerdiumService {
+        service_id: column(&["serviceId", "service_id"]),
+        name: column(&["name"]),
+        recipe_id: column(&["recipeId", "recipe_id"]),
+        settings: serde_json::from_str(&column(&["settings"])).unwrap_or(Value::Null),
+    }
+}
+
+/// package.json da recipe do serviço (recipes instaladas ou de desenvolvimento)
+fn read_recipe(ferdium_dir: &Path, recipe_id: &str) -> Option<Value> {
+    if !is_safe_name(recipe_id) {
+        return None;
+    }
+
+    let recipes_dir = ferdium_dir.join("recipes");
+    [recipes_dir.join(recipe_id), recipes_dir.join("dev").join(recipe_id)]
+        .iter()
+        .filter_map(|dir| fs::read_to_string(dir.join("package.json")).ok())
+        .find_map(|json| serde_json::from_str(&json).ok())
+}
+
+/// URL do serviço: URL customizada, ou a `serviceURL` da recipe com o time aplicado
+fn service_url(settings: &Value, recipe: Option<&Value>) -> Option<String> {
+    let non_empty = |value: Option<&Value>| {
+        value
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+
+    if let Some(custom_url) = non_empty(settings.get("customUrl")) {
+        return Some(custom_url);
+    }
+
+    let service_url = non_empty(recipe?.pointer("/config/serviceURL"))?;
+    if service_url.contains("{teamId}") {
+        let team = non_empty(settings.get("team"))?;
+        return Some(service_url.replace("{teamId}", &team));
+    }
+    Some(service_url)
+}
+
+/// Ícone customizado (`icons/{iconId}`) ou, na falta dele, o ícone da recipe
+fn find_icon(ferdium_dir: &Path, service: &FerdiumService) -> Option<PathBuf> {
+    let icon_id = service
+        .settings
+        .get("iconId")
+        .and_then(Value::as_str)
+        .filter(|id| is_safe_name(id));
+
+    if let Some(icon_id) = icon_id {
+        let icons_dir = ferdium_dir.join("icons");
+        let exact = icons_dir.join(icon_id);
+        if exact.is_file() {
+            return Some(exact);
+        }
+        let with_extension = fs::read_dir(&icons_dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| path.file_stem().is_some_and(|stem| stem == icon_id));
+        if with_extension.is_some() {
+            return with_extension;
+        }
+    }
+
+    if !is_safe_name(&service.recipe_id) {
+        return None;
+    }
+    let recipe_icon = ferdium_dir.join("recipes").join(&service.recipe_id).join("icon.svg");
+    recipe_icon.is_file().then_some(recipe_icon)
+}
+
+/// Converte os cookies da partição Chromium do serviço para `profiles/{uuid}/cookies`.
+/// Retorna quantos cookies foram gravados.
+fn copy_cookies(ferdium_dir: &Path, service_id: &str, profile_dir: &Path) -> Result<usize, Error> {
+    let partition = ferdium_dir.join("Partitions").join(format!("service-{}", service_id));
+    let Some(db_path) = [partition.join("Network").join("Cookies"), partition.join("Cookies")]
+        .into_iter()
+        .find(|path| path.is_file())
+    else {
+        return Ok(0);
+    };
+
+    let connection = Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut statement = connection
+        .prepare("SELECT host_key, name, value, path, expires_utc, is_secure, is_httponly FROM cookies")?;
+    let cookies = statement
+        .query_map([], |row| {
+            Ok(ChromiumCookie {
+                host: row.get(0)?,
+                name: row.get(1)?,
+                value: row.get(2)?,
+                path: row.get(3)?,
+                expires_utc: row.get(4)?,
+                secure: row.get::<_, i64>(5)? != 0,
+                http_only: row.get::<_, i64>(6)? != 0,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (text, count) = cookies_to_netscape(&cookies);
+    if count > 0 {
+        fs::write(profile_dir.join("cookies"), text)?;
+    }
+    Ok(count)
+}
+
+/// Gera um arquivo no formato cookies.txt (lido pelo libsoup), apenas com
+/// cookies persistentes em texto puro
+fn cookies_to_netscape(cookies: &[ChromiumCookie]) -> (String, usize) {
+    let mut lines = vec!["# Netscape HTTP Cookie File".to_string()];
+
+    for cookie in cookies {
+        // Cookies de sessão não são persistidos; valor vazio indica cookie criptografado
+        if cookie.expires_utc <= 0 || cookie.value.is_empty() {
+            continue;
+        }
+
+        let expires = cookie.expires_utc / 1_000_000 - CHROMIUM_EPOCH_OFFSET_SECS;
+        let domain = if cookie.http_only {
+            format!("#HttpOnly_{}", cookie.host)
+        } else {
+            cookie.host.clone()
+        };
+        let flag = |value: bool| if value { "TRUE" } else { "FALSE" };
+
+        lines.push(format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            domain,
+            flag(cookie.host.starts_with('.')),
+            cookie.path,
+            flag(cookie.secure),
+            expires,
+            cookie.name,
+            cookie.value
+        ));
+    }
+
+    let count = lines.len() - 1;
+    lines.push(String::new());
+    (lines.join("\n"), count)
+}
+
+fn bool_setting(settings: &Value, key: &str, default: bool) -> bool {
+    settings.get(key).and_then(Value::as_bool).unwrap_or(default)
+}
+
+/// Identificadores usados como nomes de arquivo/pasta não podem sair do diretório
+fn is_safe_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_mapping() {
+        let columns = HashMap::from([
+            ("serviceId".to_string(), "0f6c1f6e-2b39-4ab3-a3b4-7b1b0b0f6b1a".to_string()),
+            ("name".to_string(), "Slack Trabalho".to_string()),
+            ("recipeId".to_string(), "slack".to_string()),
+            ("settings".to_string(), r#"{"team": "acme", "isMuted": true, "order": 2}"#.to_string()),
+        ]);
+        let service = service_from_columns(&columns);
+        assert_eq!(service.recipe_id, "slack");
+        assert!(bool_setting(&service.settings, "isMuted", false));
+        assert!(bool_setting(&service.settings, "isEnabled", true));
+
+        let recipe = serde_json::json!({ "config": { "serviceURL": "https://{teamId}.slack.com" } });
+        assert_eq!(service_url(&service.settings, Some(&recipe)).as_deref(), Some("https://acme.slack.com"));
+
+        let custom = serde_json::json!({ "customUrl": " https://chat.example.com " });
+        assert_eq!(service_url(&custom, Some(&recipe)).as_deref(), Some("https://chat.example.com"));
+
+        // Recipe com time, mas sem time configurado
+        assert_eq!(service_url(&Value::Null, Some(&recipe)), None);
+        assert_eq!(service_url(&Value::Null, None), None);
+    }
+
+    #[test]
+    fn test_cookies_to_netscape() {
+        let expires_unix = 1_900_000_000;
+        let cookie = |name: &str, value: &str, expires_utc: i64| ChromiumCookie {
+            host: ".example.com".to_string(),
+            name: name.to_string(),
+            value: value.to_string(),
+            path: "/".to_string(),
+            expires_utc,
+            secure: true,
+            http_only: name == "sid",
+        };
+        let persistent = (expires_unix + CHROMIUM_EPOCH_OFFSET_SECS) * 1_000_000;
+
+        let (text, count) = cookies_to_netscape(&[
+            cookie("sid", "abc", persistent),
+            cookie("lang", "pt", persistent),
+            cookie("session", "tmp", 0),
+            cookie("encrypted", "", persistent),
+        ]);
+
+        assert_eq!(count, 2);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[1], "#HttpOnly_.example.com\tTRUE\t/\tTRUE\t1900000000\tsid\tabc");
+        assert_eq!(lines[2], ".example.com\tTRUE\t/\tTRUE\t1900000000\tlang\tpt");
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn test_is_safe_name() {
+        assert!(is_safe_name("whatsapp"));
+        assert!(!is_safe_name(".."));
+        assert!(!is_safe_name("../../etc"));
+        assert!(!is_safe_name(""));
+    }
+}
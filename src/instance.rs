@@ -0,0 +1,86 @@
+use std::io::{Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+
+use crate::paths::Paths;
+use crate::Error;
+
+/// Argumento que pede à instância em execução para mostrar/ocultar a janela.
+/// No Wayland, onde o atalho global não funciona, basta associá-lo a um atalho
+/// do ambiente de trabalho.
+pub const TOGGLE_ARG: &str = "--toggle";
+const TOGGLE_COMMAND: &str = "toggle";
+
+/// Envia o pedido de mostrar/ocultar. Retorna `false` se não houver instância em execução.
+pub fn send_toggle(paths: &Paths) -> Result<bool, Error> {
+    let mut stream = match UnixStream::connect(paths.instance_socket()) {
+        Ok(stream) => stream,
+        Err(e) => {
+            log::debug!("No running instance: {}", e);
+            return Ok(false);
+        }
+    };
+    stream.write_all(TOGGLE_COMMAND.as_bytes())?;
+    Ok(true)
+}
+
+/// Atende os pedidos de outras execuções em uma thread própria
+pub fn listen(paths: &Paths, on_toggle: impl Fn() + Send + 'static) -> Result<(), Error> {
+    let socket = paths.instance_socket();
+    if socket.exists() {
+        // Outra instância usando o mesmo diretório de dados continua com o socket
+        if UnixStream::connect(&socket).is_ok() {
+            log::warn!("Another instance is listening on {:?}, --toggle will reach it instead", socket);
+            return Ok(());
+        }
+        // Socket deixado por uma execução encerrada sem limpeza
+        std::fs::remove_file(&socket)?;
+    }
+
+    std::fs::create_dir_all(paths.data_dir())?;
+    let listener = UnixListener::bind(&socket)?;
+    log::debug!("Listening for instance commands on {:?}", socket);
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut command = String::new();
+            match stream.and_then(|mut stream| stream.read_to_string(&mut command)) {
+                Ok(_) if command.trim() == TOGGLE_COMMAND => on_toggle(),
+                // Conexão vazia: outra instância verificando se esta está em execução
+                Ok(_) if command.is_empty() => {}
+                Ok(_) => log::warn!("Unknown instance command: {:?}", command),
+                Err(e) => log::warn!("Failed to read instance command: {}", e),
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_toggle_reaches_listener() {
+        let dir = std::env::temp_dir().join(format!("feather-alloy-test-{}", uuid::Uuid::new_v4()));
+        let paths = Paths::new(&dir);
+        assert!(!send_toggle(&paths).unwrap());
+
+        // Um socket antigo, sem ninguém escutando, é substituído
+        std::fs::create_dir_all(&dir).unwrap();
+        drop(UnixListener::bind(paths.instance_socket()).unwrap());
+
+        let (sender, receiver) = mpsc::channel();
+        listen(&paths, move || sender.send(()).unwrap()).unwrap();
+        assert!(send_toggle(&paths).unwrap());
+        receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        // Uma segunda instância não rouba o socket da primeira
+        listen(&paths, || panic!("second listener")).unwrap();
+        assert!(send_toggle(&paths).unwrap());
+        receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
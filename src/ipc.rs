@@ -36,28 +36,88 @@ pub enum IpcMessage {
     EditProfile {
         uuid: String,
     },
+    ReorderProfiles {
+        uuids: Vec<String>,
+    },
+    
+    // Workspaces
+    GetWorkspaces,
+    SwitchWorkspace {
+        uuid: Option<String>,
+    },
+    AddWorkspace {
+        name: String,
+    },
+    RenameWorkspace {
+        uuid: String,
+        name: String,
+    },
+    RemoveWorkspace {
+        uuid: String,
+    },
     
     // Edição de Perfil
+    GetProfile {
+        uuid: String,
+    },
     SelectIcon,
     UpdateProfile {
         uuid: String,
-        name: String,
-        url: String,
-        icon_path: Option<String>,
-        user_agent: Option<String>,
+        #[serde(flatten)]
+        changes: crate::profile::ProfileUpdate,
     },
     
     // Configurações
     ShowSettings,
     GetSettings,
     UpdateSettings {
-        minimize_on_open: bool,
-        minimize_on_close: bool,
-        hide_on_close: bool,
-        enable_tray: bool,
+        #[serde(flatten)]
+        settings: crate::profile::AppSettings,
+    },
+    SelectDownloadDir,
+    ExportData {
+        include_sessions: bool,
     },
+    ImportData {
+        mode: crate::persistence::ImportMode,
+    },
+    ImportFerdium {
+        copy_sessions: bool,
+    },
+    OpenLogs,
     QuitApp,
     
+    // Downloads
+    ShowDownloads,
+    GetDownloads,
+    OpenDownload {
+        id: u64,
+    },
+    ShowDownloadInFolder {
+        id: u64,
+    },
+    OpenDownloadsFolder,
+    ClearDownloads,
+    
+    // Mensagens das webviews de perfis (conteúdo remoto)
+    ShowNotification {
+        title: String,
+        #[serde(default)]
+        body: String,
+    },
+    
+    // Atalho de teclado interceptado em qualquer webview ("Ctrl+Shift+Tab")
+    Shortcut {
+        combo: String,
+    },
+    
+    // Paleta de comandos: perfis do mais recente ao mais antigo
+    GetPalette,
+    // Exibe o perfil com a página recarregada (carregada uma única vez se a webview não existir)
+    ShowReloadedProfile {
+        uuid: String,
+    },
+    
     // Mensagens do Backend para a Toolbar
     ProfileAdded {
         profile: crate::profile::WebProfile,
@@ -71,22 +131,28 @@ pub enum IpcMessage {
     ProfilesList {
         profiles: Vec<crate::profile::WebProfile>,
     },
-    SettingsData {
-        settings: crate::profile::AppSettings,
+    HibernationChanged {
+        hibernated: Vec<String>,
+    },
+    ProfileActivated {
+        uuid: String,
+    },
+    UnreadCountsUpdated {
+        counts: std::collections::HashMap<String, u32>,
+    },
+    WorkspacesUpdated {
+        #[serde(flatten)]
+        workspaces: crate::profile::Workspaces,
     },
     
     // Mensagens do Backend para a Content Webview
-    NavigateToUrl {
-        url: String,
-        user_agent: Option<String>,
-    },
     ShowWelcome,
     
-    // Respostas genéricas
-    Success {
+    // Avisos do backend exibidos como toast na toolbar ou no conteúdo
+    Error {
         message: String,
     },
-    Error {
+    Notice {
         message: String,
     },
 }
@@ -103,77 +169,275 @@ impl IpcMessage {
     }
 }
 
-/// Handler de IPC que processa mensagens
-pub struct IpcHandler {
-    state: crate::profile::AppState,
+/// Webview de origem de uma mensagem (e destino da resposta, nas requisições)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpcSource {
+    Toolbar,
+    Content,
+    /// Webview de perfil, com conteúdo remoto
+    Profile,
 }
 
-impl IpcHandler {
-    pub fn new(state: crate::profile::AppState) -> Self {
-        Self { state }
+impl IpcSource {
+    /// Mensagens que cada webview pode enviar ao backend
+    pub fn allows(self, message: &IpcMessage) -> bool {
+        use IpcMessage::*;
+
+        match self {
+            IpcSource::Toolbar => matches!(
+                message,
+                ShowAddProfileForm
+                    | ShowProfile { .. }
+                    | ShowWelcome
+                    | GetProfiles
+                    | ShowSettings
+                    | ShowDownloads
+                    | ShowProfileContextMenu { .. }
+                    | ReloadProfile { .. }
+                    | UpdateProfileIcon { .. }
+                    | EditProfile { .. }
+                    | RemoveProfile { .. }
+                    | ReorderProfiles { .. }
+                    | GetWorkspaces
+                    | SwitchWorkspace { .. }
+                    | Shortcut { .. }
+            ),
+            IpcSource::Content => matches!(
+                message,
+                AddProfile { .. }
+                    | CancelAddProfile
+                    | GetProfile { .. }
+                    | UpdateProfile { .. }
+                    | SelectIcon
+                    | GetWorkspaces
+                    | AddWorkspace { .. }
+                    | RenameWorkspace { .. }
+                    | RemoveWorkspace { .. }
+                    | GetSettings
+                    | UpdateSettings { .. }
+                    | SelectDownloadDir
+                    | ExportData { .. }
+                    | ImportData { .. }
+                    | ImportFerdium { .. }
+                    | GetDownloads
+                    | OpenDownload { .. }
+                    | ShowDownloadInFolder { .. }
+                    | OpenDownloadsFolder
+                    | OpenLogs
+                    | ClearDownloads
+                    | ShowWelcome
+                    | QuitApp
+                    | Shortcut { .. }
+                    // Ações disparadas pela paleta de comandos
+                    | GetPalette
+                    | ShowProfile { .. }
+                    | ShowReloadedProfile { .. }
+                    | EditProfile { .. }
+                    | ShowAddProfileForm
+                    | ShowSettings
+                    | ShowDownloads
+            ),
+            // Uma página pode forjar qualquer atalho configurado: trocar de perfil,
+            // recarregar o atual e abrir as configurações ou a paleta. Nenhum deles
+            // altera dados; alterações continuam exigindo a interface embutida.
+            IpcSource::Profile => matches!(message, ShowNotification { .. } | Shortcut { .. }),
+        }
     }
-    
-    /// Processa uma mensagem IPC e retorna a resposta
-    pub fn handle_message(&self, message: IpcMessage) -> Option<IpcMessage> {
-        match message {
-            IpcMessage::AddProfile { name, url, icon_path, user_agent } => {
-                let profile = crate::profile::WebProfile::new(name, url, icon_path, user_agent);
-                
-                let mut data = self.state.lock().unwrap();
-                data.profiles.push(profile.clone());
-                drop(data);
-                
-                Some(IpcMessage::ProfileAdded { profile })
-            }
-            
-            IpcMessage::RemoveProfile { uuid } => {
-                let mut data = self.state.lock().unwrap();
-                data.profiles.retain(|p| p.uuid != uuid);
-                drop(data);
-                
-                Some(IpcMessage::ProfileRemoved { uuid })
-            }
-            
-            IpcMessage::SelectIcon => None,
-
-            IpcMessage::GetProfiles => {
-                let data = self.state.lock().unwrap();
-                let profiles_list = data.profiles.clone();
-                drop(data);
-                
-                Some(IpcMessage::ProfilesList { profiles: profiles_list })
-            }
-            
-            IpcMessage::ShowProfile { uuid } => {
-                let data = self.state.lock().unwrap();
-                if let Some(profile) = data.profiles.iter().find(|p| p.uuid == uuid) {
-                    let url = profile.url.clone();
-                    let user_agent = profile.user_agent.clone();
-                    drop(data);
-                    
-                    Some(IpcMessage::NavigateToUrl { url, user_agent })
-                } else {
-                    drop(data);
-                    Some(IpcMessage::Error {
-                        message: "Perfil não encontrado".to_string(),
-                    })
-                }
-            }
-            
-            IpcMessage::GetSettings => {
-                let data = self.state.lock().unwrap();
-                let settings = data.settings.clone();
-                drop(data);
-                
-                Some(IpcMessage::SettingsData { settings })
-            }
-
-            
-            IpcMessage::ShowProfileContextMenu { .. } => None,
-            
-            // Outras mensagens não precisam de resposta ou são apenas para notificação
-
-            _ => None,
+
+    /// Verifica a página de origem e a permissão da mensagem.
+    ///
+    /// Toolbar e conteúdo só aceitam mensagens da interface embutida: se a
+    /// webview tiver navegado para outro endereço, tudo é recusado. Perfis
+    /// podem estar em qualquer origem, mas têm o conjunto mínimo de mensagens.
+    pub fn authorize(self, origin: &http::Uri, message: &IpcMessage) -> Result<(), String> {
+        if self != IpcSource::Profile && !crate::assets::is_app_uri(origin) {
+            return Err(format!("untrusted origin {}", origin));
+        }
+        if !self.allows(message) {
+            return Err(format!("message not allowed from {:?}", self));
+        }
+        Ok(())
+    }
+}
+
+/// Requisição com resposta, enviada por `backend.call()` (ui/common.js)
+/// como `{ "id": 1, "message": { "type": ..., "payload": ... } }`
+#[derive(Debug, Clone, Deserialize)]
+pub struct IpcRequest {
+    pub id: u64,
+    pub message: IpcMessage,
+}
+
+impl IpcRequest {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Resposta a uma `IpcRequest`: resolve ou rejeita a Promise de mesmo `id`
+#[derive(Debug, Clone, Serialize)]
+pub struct IpcResponse {
+    pub id: u64,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "serde_json::Value::is_null")]
+    pub data: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl IpcResponse {
+    pub fn new(id: u64, result: Result<serde_json::Value, crate::Error>) -> Self {
+        Self::from_result(id, result.map_err(|e| e.to_string()))
+    }
+
+    /// Resposta com o erro já formatado (ex.: operações concluídas em outra thread)
+    pub fn from_result(id: u64, result: Result<serde_json::Value, String>) -> Self {
+        match result {
+            Ok(data) => Self { id, ok: true, data, error: None },
+            Err(error) => Self { id, ok: false, data: serde_json::Value::Null, error: Some(error) },
         }
     }
+
+    /// Script que entrega a resposta ao dispatcher da página
+    pub fn to_script(&self) -> Result<String, serde_json::Error> {
+        Ok(format!(
+            "if (window.backend) {{ window.backend.resolve({}); }}",
+            serde_json::to_string(self)?
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_envelope() {
+        let request = IpcRequest::from_json(r#"{"id": 7, "message": {"type": "GetProfile", "payload": {"uuid": "abc"}}}"#).unwrap();
+        assert_eq!(request.id, 7);
+        assert!(matches!(request.message, IpcMessage::GetProfile { ref uuid } if uuid == "abc"));
+
+        // Mensagens sem id continuam sendo apenas notificações
+        assert!(IpcRequest::from_json(r#"{"type": "GetSettings"}"#).is_err());
+        assert!(IpcMessage::from_json(r#"{"type": "GetSettings"}"#).is_ok());
+    }
+
+    fn app_origin() -> http::Uri {
+        crate::assets::url("ui/content/settings.html").parse().unwrap()
+    }
+
+    fn remote_origin() -> http::Uri {
+        "https://evil.example.com/".parse().unwrap()
+    }
+
+    fn message(json: &str) -> IpcMessage {
+        IpcMessage::from_json(json).unwrap()
+    }
+
+    #[test]
+    fn test_toolbar_capabilities() {
+        let origin = app_origin();
+        let allowed = [
+            IpcMessage::RemoveProfile { uuid: "abc".to_string() },
+            message(r#"{"type": "SwitchWorkspace", "payload": {"uuid": null}}"#),
+            message(r#"{"type": "ReorderProfiles", "payload": {"uuids": ["b", "a"]}}"#),
+            message(r#"{"type": "Shortcut", "payload": {"combo": "Ctrl+1"}}"#),
+        ];
+        for message in &allowed {
+            assert!(IpcSource::Toolbar.authorize(&origin, message).is_ok(), "{:?}", message);
+        }
+
+        let denied = [
+            IpcMessage::QuitApp,
+            IpcMessage::RemoveWorkspace { uuid: "abc".to_string() },
+            IpcMessage::GetPalette,
+        ];
+        for message in &denied {
+            assert!(IpcSource::Toolbar.authorize(&origin, message).is_err(), "{:?}", message);
+        }
+    }
+
+    #[test]
+    fn test_content_capabilities() {
+        let origin = app_origin();
+        let allowed = [
+            IpcMessage::QuitApp,
+            IpcMessage::GetPalette,
+            IpcMessage::ShowReloadedProfile { uuid: "abc".to_string() },
+        ];
+        for message in &allowed {
+            assert!(IpcSource::Content.authorize(&origin, message).is_ok(), "{:?}", message);
+        }
+
+        // Recarregar pela paleta passa por ShowReloadedProfile
+        let denied = [
+            IpcMessage::ReloadProfile { uuid: "abc".to_string() },
+            message(r#"{"type": "ReorderProfiles", "payload": {"uuids": ["b", "a"]}}"#),
+        ];
+        for message in &denied {
+            assert!(IpcSource::Content.authorize(&origin, message).is_err(), "{:?}", message);
+        }
+    }
+
+    #[test]
+    fn test_profile_capabilities() {
+        // Perfis: qualquer origem, apenas notificações e atalhos
+        let origin = remote_origin();
+        let notification = IpcMessage::ShowNotification { title: "Oi".to_string(), body: String::new() };
+        assert!(IpcSource::Profile.authorize(&origin, &notification).is_ok());
+        let shortcut = message(r#"{"type": "Shortcut", "payload": {"combo": "Ctrl+1"}}"#);
+        assert!(IpcSource::Profile.authorize(&origin, &shortcut).is_ok());
+
+        let denied = [
+            IpcMessage::QuitApp,
+            IpcMessage::ShowReloadedProfile { uuid: "abc".to_string() },
+        ];
+        for message in &denied {
+            assert!(IpcSource::Profile.authorize(&origin, message).is_err(), "{:?}", message);
+        }
+        // Nem mesmo na origem da interface embutida
+        assert!(IpcSource::Profile.authorize(&app_origin(), &IpcMessage::GetSettings).is_err());
+    }
+
+    #[test]
+    fn test_untrusted_origin() {
+        // Toolbar e conteúdo recusam tudo se tiverem navegado para fora da interface
+        let origin = remote_origin();
+        assert!(IpcSource::Content.authorize(&origin, &IpcMessage::QuitApp).is_err());
+        assert!(IpcSource::Toolbar.authorize(&origin, &IpcMessage::RemoveProfile { uuid: "abc".to_string() }).is_err());
+    }
+
+    #[test]
+    fn test_outgoing_messages() {
+        // Mensagens do backend para a interface nunca são aceitas como entrada
+        let origin = app_origin();
+        let outgoing = IpcMessage::ProfileRemoved { uuid: "abc".to_string() };
+        assert!(IpcSource::Toolbar.authorize(&origin, &outgoing).is_err());
+        let toast = IpcMessage::Error { message: "Falha ao salvar".to_string() };
+        assert!(IpcSource::Content.authorize(&origin, &toast).is_err());
+        assert_eq!(
+            toast.to_json().unwrap(),
+            r#"{"type":"Error","payload":{"message":"Falha ao salvar"}}"#
+        );
+        let workspaces = IpcMessage::WorkspacesUpdated { workspaces: Default::default() };
+        assert!(IpcSource::Toolbar.authorize(&origin, &workspaces).is_err());
+        assert_eq!(
+            workspaces.to_json().unwrap(),
+            r#"{"type":"WorkspacesUpdated","payload":{"workspaces":[],"active":null}}"#
+        );
+    }
+
+    #[test]
+    fn test_response_script() {
+        let ok = IpcResponse::new(1, Ok(serde_json::json!({ "enable_tray": true })));
+        assert_eq!(
+            ok.to_script().unwrap(),
+            r#"if (window.backend) { window.backend.resolve({"id":1,"ok":true,"data":{"enable_tray":true}}); }"#
+        );
+
+        let error = IpcResponse::new(2, Err(crate::Error::ProfileNotFound("abc".to_string())));
+        assert!(!error.ok);
+        assert_eq!(error.error.as_deref(), Some("Perfil não encontrado: abc"));
+        assert!(!error.to_script().unwrap().contains("\"data\""));
+    }
 }
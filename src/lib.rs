@@ -1,9 +1,26 @@
+pub mod error;
 pub mod profile;
+pub mod controller;
 pub mod ipc;
+pub mod logging;
+pub mod paths;
+#[cfg(unix)]
+pub mod instance;
+pub mod shortcuts;
 pub mod window_manager;
 pub mod persistence;
+pub mod migrations;
 pub mod context_menu;
 pub mod favicon;
+pub mod unread;
+pub mod tray_badge;
+pub mod notifications;
+pub mod links;
+pub mod downloads;
+pub mod ferdium;
+pub mod assets;
+
+pub use error::Error;
 
 use profile::create_app_state;
 use window_manager::{WindowManager, AppEvent};
@@ -12,6 +29,22 @@ use window_manager::{WindowManager, AppEvent};
 use gtk;
 
 pub fn run() {
+    let paths = paths::Paths::from_environment().expect("Failed to resolve data directory");
+
+    // O nível detalhado é aplicado depois de carregar as configurações
+    logging::init(&paths, false);
+    log::info!("Using data directory {:?}", paths.data_dir());
+
+    // `--toggle` só repassa o pedido; sem instância em execução, abre normalmente
+    #[cfg(unix)]
+    if std::env::args().any(|arg| arg == instance::TOGGLE_ARG) {
+        match instance::send_toggle(&paths) {
+            Ok(true) => return,
+            Ok(false) => log::info!("No running instance to toggle, starting"),
+            Err(e) => log::warn!("Failed to reach the running instance: {}", e),
+        }
+    }
+
     // Inicializar GTK no Linux
     #[cfg(target_os = "linux")]
     {
@@ -24,8 +57,18 @@ pub fn run() {
     // Criar event loop com eventos customizados
     let event_loop = tao::event_loop::EventLoopBuilder::<AppEvent>::with_user_event().build();
 
+    #[cfg(unix)]
+    {
+        let proxy = event_loop.create_proxy();
+        if let Err(e) = instance::listen(&paths, move || {
+            let _ = proxy.send_event(AppEvent::SummonWindow);
+        }) {
+            log::warn!("Failed to listen for --toggle: {}", e);
+        }
+    }
+
     // Criar window manager com as duas webviews
-    let window_manager = WindowManager::new(&event_loop, state)
+    let window_manager = WindowManager::new(&event_loop, state, paths)
         .expect("Failed to create window manager");
 
     // Executar event loop
@@ -0,0 +1,151 @@
+use std::process::Command;
+
+/// Domínios de login (OAuth/SSO) que podem abrir como janela filha do perfil
+pub const AUTH_DOMAINS: &[&str] = &[
+    "accounts.google.com",
+    "login.microsoftonline.com",
+    "login.microsoft.com",
+    "login.live.com",
+    "appleid.apple.com",
+    "okta.com",
+    "auth0.com",
+    "onelogin.com",
+];
+
+/// Onde uma URL aberta a partir de um perfil deve ser exibida
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// Domínio do próprio serviço (ou permitido pelo usuário): abre na webview
+    Internal,
+    /// Fluxo de login: abre em janela filha que compartilha o WebContext
+    Auth,
+    /// Qualquer outro destino: abre no navegador do sistema
+    External,
+}
+
+/// Regras de navegação de um perfil
+#[derive(Debug, Clone)]
+pub struct LinkPolicy {
+    service_domain: Option<String>,
+    allowed_domains: Vec<String>,
+}
+
+impl LinkPolicy {
+    pub fn new(profile_url: &str, allowed_domains: &[String]) -> Self {
+        let service_domain = url::Url::parse(profile_url)
+            .ok()
+            .and_then(|u| u.host_str().map(base_domain));
+
+        Self {
+            service_domain,
+            allowed_domains: allowed_domains.iter().map(|d| normalize_domain(d)).filter(|d| !d.is_empty()).collect(),
+        }
+    }
+
+    pub fn classify(&self, target_url: &str) -> LinkTarget {
+        let Ok(url) = url::Url::parse(target_url) else {
+            return LinkTarget::Internal;
+        };
+
+        match url.scheme() {
+            "http" | "https" => {}
+            "about" | "data" | "blob" | "javascript" => return LinkTarget::Internal,
+            // mailto:, tel:, magnet:, etc. ficam com o sistema
+            _ => return LinkTarget::External,
+        }
+
+        let Some(host) = url.host_str().map(|h| h.to_lowercase()) else {
+            return LinkTarget::Internal;
+        };
+
+        let is_service = self.service_domain.as_deref().is_some_and(|d| host_matches(&host, d));
+        if is_service || self.allowed_domains.iter().any(|d| host_matches(&host, d)) {
+            LinkTarget::Internal
+        } else if AUTH_DOMAINS.iter().any(|d| host_matches(&host, d)) {
+            LinkTarget::Auth
+        } else {
+            LinkTarget::External
+        }
+    }
+}
+
+/// Aceita entradas como "*.example.com", ".example.com" ou "https://example.com/"
+fn normalize_domain(domain: &str) -> String {
+    let domain = domain.trim().to_lowercase();
+    let domain = domain
+        .strip_prefix("https://")
+        .or_else(|| domain.strip_prefix("http://"))
+        .unwrap_or(&domain);
+    let domain = domain.split('/').next().unwrap_or_default();
+    domain.trim_start_matches("*.").trim_start_matches('.').to_string()
+}
+
+fn host_matches(host: &str, domain: &str) -> bool {
+    host == domain || host.ends_with(&format!(".{}", domain))
+}
+
+/// Domínio registrável aproximado: últimos dois rótulos, ou três para
+/// sufixos como "com.br" e "co.uk" (web.whatsapp.com -> whatsapp.com)
+fn base_domain(host: &str) -> String {
+    let host = host.to_lowercase();
+    let labels: Vec<&str> = host.split('.').collect();
+    if labels.len() <= 2 {
+        return host;
+    }
+
+    let tld = labels[labels.len() - 1];
+    let second = labels[labels.len() - 2];
+    let take = if tld.len() == 2 && second.len() <= 3 { 3 } else { 2 };
+    labels[labels.len() - take..].join(".")
+}
+
+/// Abre uma URL ou caminho com o aplicativo padrão do sistema (xdg-open no Linux)
+pub fn open_external(target: &str) -> std::io::Result<()> {
+    #[cfg(target_os = "linux")]
+    let mut child = Command::new("xdg-open").arg(target).spawn()?;
+    #[cfg(target_os = "windows")]
+    let mut child = Command::new("cmd").args(["/C", "start", "", target]).spawn()?;
+    #[cfg(target_os = "macos")]
+    let mut child = Command::new("open").arg(target).spawn()?;
+
+    log::debug!("Opened externally: {}", target);
+
+    // Evitar processos zumbis
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_service_and_external() {
+        let policy = LinkPolicy::new("https://web.whatsapp.com", &[]);
+        assert_eq!(policy.classify("https://web.whatsapp.com/chat"), LinkTarget::Internal);
+        assert_eq!(policy.classify("https://static.whatsapp.com/x.js"), LinkTarget::Internal);
+        assert_eq!(policy.classify("https://example.com/article"), LinkTarget::External);
+        assert_eq!(policy.classify("mailto:someone@example.com"), LinkTarget::External);
+        assert_eq!(policy.classify("about:blank"), LinkTarget::Internal);
+        assert_eq!(policy.classify("https://notwhatsapp.com"), LinkTarget::External);
+    }
+
+    #[test]
+    fn test_classify_allowed_and_auth() {
+        let allowed = vec!["*.slack-edge.com".to_string(), "https://files.example.org/".to_string()];
+        let policy = LinkPolicy::new("https://app.slack.com/client", &allowed);
+        assert_eq!(policy.classify("https://a.slack-edge.com/img.png"), LinkTarget::Internal);
+        assert_eq!(policy.classify("https://files.example.org/doc.pdf"), LinkTarget::Internal);
+        assert_eq!(policy.classify("https://accounts.google.com/o/oauth2/auth"), LinkTarget::Auth);
+        assert_eq!(policy.classify("https://google.com"), LinkTarget::External);
+    }
+
+    #[test]
+    fn test_base_domain() {
+        assert_eq!(base_domain("web.whatsapp.com"), "whatsapp.com");
+        assert_eq!(base_domain("mail.empresa.com.br"), "empresa.com.br");
+        assert_eq!(base_domain("localhost"), "localhost");
+    }
+}
@@ -0,0 +1,193 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::paths::Paths;
+
+const LOG_FILE_NAME: &str = "feather-alloy.log";
+/// Tamanho a partir do qual o arquivo atual vira `.1`
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+/// Arquivos antigos mantidos (`feather-alloy.log.1` até `.N`)
+const MAX_LOG_FILES: usize = 3;
+/// Prefixo dos targets da aplicação; as dependências só registram avisos e erros
+const APP_TARGET: &str = "feather_alloy";
+
+/// Instala o logger (stderr + arquivo rotativo) e registra panics no log.
+/// Se a pasta de logs não puder ser criada, o log vai apenas para o stderr.
+pub fn init(paths: &Paths, verbose: bool) {
+    let dir = paths.logs_dir();
+    let file = fs::create_dir_all(&dir)
+        .and_then(|_| RotatingFile::open(dir.join(LOG_FILE_NAME), MAX_LOG_SIZE))
+        .map_err(|e| eprintln!("Failed to open log file: {}", e))
+        .ok();
+
+    let logger = Logger { file: Mutex::new(file) };
+    if log::set_boxed_logger(Box::new(logger)).is_err() {
+        return;
+    }
+    set_verbose(verbose);
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        log::error!("{}", info);
+        default_hook(info);
+    }));
+}
+
+/// Alterna entre o nível `Info` e o detalhado (`Debug`)
+pub fn set_verbose(verbose: bool) {
+    log::set_max_level(if verbose { LevelFilter::Debug } else { LevelFilter::Info });
+}
+
+struct Logger {
+    file: Mutex<Option<RotatingFile>>,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target().starts_with(APP_TARGET) || metadata.level() <= Level::Warn
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!(
+            "{} {:<5} {}: {}",
+            format_timestamp(unix_now()),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        eprintln!("{}", line);
+
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            // Sem onde relatar: falhas do próprio log são ignoradas
+            let _ = file.write_line(&line);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            let _ = file.file.flush();
+        }
+    }
+}
+
+/// Arquivo de log que é rotacionado ao passar de `max_size` bytes
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf, max_size: u64) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path, file, size, max_size })
+    }
+
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_size {
+            self.rotate()?;
+        }
+        writeln!(self.file, "{}", line)?;
+        self.size += len;
+        Ok(())
+    }
+
+    /// `.log` vira `.log.1`, `.log.1` vira `.log.2`... e o mais antigo é descartado
+    fn rotate(&mut self) -> std::io::Result<()> {
+        for index in (1..MAX_LOG_FILES).rev() {
+            let from = rotated_path(&self.path, index);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, index + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        self.file = File::create(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Data e hora UTC no formato `2024-01-31T12:34:56Z`
+fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let time = secs % 86_400;
+
+    // Conversão de dias desde 1970-01-01 para data civil (algoritmo de Howard Hinnant)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_timestamp(1_706_704_496), "2024-01-31T12:34:56Z");
+    }
+
+    #[test]
+    fn test_rotating_file() {
+        let dir = std::env::temp_dir().join(format!("feather-alloy-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(LOG_FILE_NAME);
+
+        let mut file = RotatingFile::open(path.clone(), 20).unwrap();
+        for line in ["first line", "second line", "third line", "fourth line", "fifth line"] {
+            file.write_line(line).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fifth line\n");
+        assert_eq!(fs::read_to_string(rotated_path(&path, 1)).unwrap(), "fourth line\n");
+        assert_eq!(fs::read_to_string(rotated_path(&path, 3)).unwrap(), "second line\n");
+        assert!(!rotated_path(&path, 4).exists());
+
+        // Reabrir continua do tamanho atual
+        let reopened = RotatingFile::open(path.clone(), 20).unwrap();
+        assert_eq!(reopened.size, "fifth line\n".len() as u64);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,178 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::Error;
+
+/// Versão atual do formato de profiles.json, settings.json e workspaces.json
+pub const CURRENT_VERSION: u64 = 1;
+
+/// Arquivo de configuração, cada um com sua sequência de migrações
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigKind {
+    Profiles,
+    Settings,
+    Workspaces,
+}
+
+type Migration = fn(Value) -> Result<Value, Error>;
+
+impl ConfigKind {
+    /// Migrações em ordem: o índice `n` converte os dados da versão `n` para `n + 1`
+    fn migrations(self) -> &'static [Migration] {
+        match self {
+            ConfigKind::Profiles => &[profiles_v0_to_v1],
+            ConfigKind::Settings => &[object_v0_to_v1],
+            // workspaces.json já nasceu na versão 1
+            ConfigKind::Workspaces => &[object_v0_to_v1],
+        }
+    }
+}
+
+/// Serializa os dados dentro do envelope `{ "version": N, "data": ... }`
+pub fn to_versioned_json<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    Ok(serde_json::to_string_pretty(&json!({
+        "version": CURRENT_VERSION,
+        "data": value,
+    }))?)
+}
+
+/// Lê um arquivo de qualquer versão conhecida, aplicando as migrações necessárias.
+///
+/// Arquivos sem envelope são tratados como versão 0. Arquivos de uma versão
+/// mais nova que a suportada são lidos como a versão atual (campos
+/// desconhecidos são ignorados); `persistence` guarda uma cópia do original
+/// antes que ele seja regravado (ver `newer_version`).
+pub fn from_versioned_json<T: DeserializeOwned>(kind: ConfigKind, json: &str) -> Result<T, Error> {
+    let (mut version, mut data) = split_envelope(serde_json::from_str(json)?);
+
+    if version > CURRENT_VERSION {
+        log::warn!(
+            "{:?} file has version {} (newer than {}), reading it as the current version",
+            kind, version, CURRENT_VERSION
+        );
+    }
+
+    let migrations = kind.migrations();
+    while version < CURRENT_VERSION {
+        data = migrations[version as usize](data)?;
+        version += 1;
+        log::info!("Migrated {:?} file to version {}", kind, version);
+    }
+
+    Ok(serde_json::from_value(data)?)
+}
+
+/// Versão do arquivo, se ele foi gravado por uma versão mais nova do aplicativo
+pub fn newer_version(json: &str) -> Option<u64> {
+    let (version, _) = split_envelope(serde_json::from_str(json).ok()?);
+    (version > CURRENT_VERSION).then_some(version)
+}
+
+fn split_envelope(value: Value) -> (u64, Value) {
+    match value {
+        Value::Object(mut map) if map.contains_key("version") && map.contains_key("data") => {
+            let version = map.get("version").and_then(Value::as_u64).unwrap_or(0);
+            (version, map.remove("data").unwrap_or(Value::Null))
+        }
+        // Versão 0: lista de perfis ou objeto de configurações sem envelope
+        other => (0, other),
+    }
+}
+
+/// v0 -> v1: perfis anteriores à hibernação não tinham `auto_hibernate`
+fn profiles_v0_to_v1(mut data: Value) -> Result<Value, Error> {
+    let profiles = data
+        .as_array_mut()
+        .ok_or_else(|| Error::Config("profiles v0: expected a list of profiles".to_string()))?;
+    for profile in profiles {
+        if let Some(profile) = profile.as_object_mut() {
+            profile.entry("auto_hibernate").or_insert(Value::Bool(true));
+        }
+    }
+    Ok(data)
+}
+
+/// v0 -> v1 de settings.json (só o envelope mudou) e de arquivos que não
+/// existiam na versão 0: confere que os dados são um objeto
+fn object_v0_to_v1(data: Value) -> Result<Value, Error> {
+    if !data.is_object() {
+        return Err(Error::Config("v0: expected an object".to_string()));
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::{AppSettings, WebProfile};
+
+    fn load_profiles(json: &str) -> Vec<WebProfile> {
+        from_versioned_json(ConfigKind::Profiles, json).unwrap()
+    }
+
+    fn load_settings(json: &str) -> AppSettings {
+        from_versioned_json(ConfigKind::Settings, json).unwrap()
+    }
+
+    #[test]
+    fn test_every_version_has_a_migration() {
+        assert_eq!(ConfigKind::Profiles.migrations().len() as u64, CURRENT_VERSION);
+        assert_eq!(ConfigKind::Settings.migrations().len() as u64, CURRENT_VERSION);
+        assert_eq!(ConfigKind::Workspaces.migrations().len() as u64, CURRENT_VERSION);
+    }
+
+    #[test]
+    fn test_load_profiles_fixtures() {
+        let initial = load_profiles(include_str!("../tests/fixtures/profiles_v0_initial.json"));
+        assert_eq!(initial.len(), 2);
+        assert!(initial.iter().all(|p| p.auto_hibernate));
+        assert_eq!(initial[1].icon_path.as_deref(), Some("profiles/b2d4f6a8-1c3e-4a5b-8d7f-9e0a1b2c3d4e/icon.png"));
+        assert!(initial[0].user_agent.is_none());
+
+        let v0 = load_profiles(include_str!("../tests/fixtures/profiles_v0.json"));
+        assert!(!v0[0].auto_hibernate);
+        assert!(v0[0].notifications_muted);
+        assert_eq!(v0[0].unread_patterns, vec![r"^\((\d+)\)".to_string()]);
+        assert_eq!(v0[0].allowed_domains, vec!["whatsapp.net".to_string()]);
+
+        let v1 = load_profiles(include_str!("../tests/fixtures/profiles_v1.json"));
+        assert_eq!(v1[0].name, "WhatsApp");
+    }
+
+    #[test]
+    fn test_load_settings_fixtures() {
+        let initial = load_settings(include_str!("../tests/fixtures/settings_v0_initial.json"));
+        assert!(initial.minimize_on_open);
+        assert_eq!(initial.hibernate_after_minutes, AppSettings::default().hibernate_after_minutes);
+        assert!(initial.download_dir.is_none());
+
+        let v0 = load_settings(include_str!("../tests/fixtures/settings_v0.json"));
+        assert_eq!(v0.hibernate_after_minutes, 10);
+        assert!(v0.downloads_per_profile);
+
+        // Campos ausentes usam os valores padrão
+        let v1 = load_settings(include_str!("../tests/fixtures/settings_v1.json"));
+        assert_eq!(v1.hibernate_after_minutes, 0);
+        assert!(v1.hide_on_close);
+    }
+
+    #[test]
+    fn test_round_trip_and_newer_version() {
+        let profiles = vec![WebProfile::new("Slack".to_string(), "https://app.slack.com".to_string(), None, None)];
+        let json = to_versioned_json(&profiles).unwrap();
+        assert!(json.contains("\"version\": 1"));
+        assert_eq!(load_profiles(&json)[0].name, "Slack");
+
+        let newer = r#"{ "version": 99, "data": { "enable_tray": false, "new_option": true } }"#;
+        assert!(!load_settings(newer).enable_tray);
+        assert_eq!(newer_version(newer), Some(99));
+        assert_eq!(newer_version(&json), None);
+        assert_eq!(newer_version("[{"), None);
+
+        let invalid = from_versioned_json::<AppSettings>(ConfigKind::Settings, "[]");
+        assert!(matches!(invalid, Err(Error::Config(_))));
+        let truncated = from_versioned_json::<Vec<WebProfile>>(ConfigKind::Profiles, "[{");
+        assert!(matches!(truncated, Err(Error::Json(_))));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::Error;
+
+/// Notificações abertas por chave (ex.: UUID do perfil) e o id dado pelo servidor.
+/// Uma nova notificação com a mesma chave substitui a anterior e reaproveita a
+/// thread que espera o clique, para que um serviço que dispara notificações em
+/// laço não crie uma thread por notificação.
+#[cfg(target_os = "linux")]
+static OPEN: std::sync::LazyLock<std::sync::Mutex<std::collections::HashMap<String, u32>>> =
+    std::sync::LazyLock::new(Default::default);
+
+/// Exibe uma notificação de desktop.
+///
+/// No Linux usa a interface D-Bus `org.freedesktop.Notifications`; `on_click`
+/// é chamado (em outra thread) quando o usuário clica na notificação. Se já
+/// houver uma notificação aberta com a mesma `key`, ela é substituída e o clique
+/// continua chamando o `on_click` da primeira.
+pub fn show_notification<F>(key: &str, summary: &str, body: &str, on_click: F) -> Result<(), Error>
+where
+    F: FnOnce() + Send + 'static,
+{
+    #[cfg(target_os = "linux")]
+    {
+        let mut open = OPEN.lock().unwrap();
+        let mut notification = notify_rust::Notification::new();
+        notification
+            .appname("Feather Alloy")
+            .summary(summary)
+            .body(body)
+            .action("default", "Abrir");
+
+        if let Some(&id) = open.get(key) {
+            notification.id(id).show().map_err(|e| Error::Notification(e.to_string()))?;
+            return Ok(());
+        }
+
+        let handle = notification.show().map_err(|e| Error::Notification(e.to_string()))?;
+        open.insert(key.to_string(), handle.id());
+        let key = key.to_string();
+
+        // wait_for_action bloqueia até a notificação ser clicada ou fechada
+        std::thread::spawn(move || {
+            handle.wait_for_action(|action| {
+                if action == "default" {
+                    on_click();
+                }
+            });
+            OPEN.lock().unwrap().remove(&key);
+        });
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (key, on_click);
+        log::info!("Desktop notifications not supported on this platform: {} - {}", summary, body);
+    }
+
+    Ok(())
+}
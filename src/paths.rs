@@ -0,0 +1,164 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::Error;
+
+/// Nome da pasta dentro do diretório de dados do sistema
+const APP_DIR_NAME: &str = "feather-alloy";
+/// Variável de ambiente com o diretório de dados
+pub const DATA_DIR_ENV: &str = "FEATHER_ALLOY_DATA_DIR";
+/// Arquivo ao lado do executável que ativa o modo portátil
+const PORTABLE_MARKER: &str = "portable";
+/// Pasta de dados do modo portátil, ao lado do executável
+const PORTABLE_DATA_DIR: &str = "data";
+
+/// Locais dos arquivos da aplicação, todos dentro de um único diretório de dados
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paths {
+    data_dir: PathBuf,
+}
+
+impl Paths {
+    pub fn new(data_dir: impl Into<PathBuf>) -> Self {
+        Self { data_dir: data_dir.into() }
+    }
+
+    /// Escolhe o diretório de dados, nesta ordem: `--data-dir <pasta>`,
+    /// `FEATHER_ALLOY_DATA_DIR`, modo portátil (`--portable` ou um arquivo
+    /// `portable` ao lado do executável) e, por fim, o diretório do sistema.
+    pub fn from_environment() -> Result<Self, Error> {
+        let args: Vec<OsString> = std::env::args_os().skip(1).collect();
+        let exe_dir = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf));
+        let paths = resolve(&args, std::env::var_os(DATA_DIR_ENV), exe_dir, dirs::data_dir())?;
+
+        // Caminhos relativos são fixados agora, antes de qualquer mudança de diretório
+        Ok(Self::new(std::env::current_dir()?.join(paths.data_dir)))
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    pub fn profiles_file(&self) -> PathBuf {
+        self.data_dir.join("profiles.json")
+    }
+
+    pub fn settings_file(&self) -> PathBuf {
+        self.data_dir.join("settings.json")
+    }
+
+    pub fn workspaces_file(&self) -> PathBuf {
+        self.data_dir.join("workspaces.json")
+    }
+
+    /// Pasta com ícone e dados de sessão de um perfil
+    pub fn profile_dir(&self, uuid: &str) -> PathBuf {
+        self.data_dir.join("profiles").join(uuid)
+    }
+
+    pub fn logs_dir(&self) -> PathBuf {
+        self.data_dir.join("logs")
+    }
+
+    /// Socket da instância em execução, usado pelo `--toggle`
+    pub fn instance_socket(&self) -> PathBuf {
+        self.data_dir.join("instance.sock")
+    }
+}
+
+fn resolve(
+    args: &[OsString],
+    env_dir: Option<OsString>,
+    exe_dir: Option<PathBuf>,
+    system_dir: Option<PathBuf>,
+) -> Result<Paths, Error> {
+    if let Some(dir) = data_dir_arg(args)? {
+        return Ok(Paths::new(dir));
+    }
+
+    if let Some(dir) = env_dir.filter(|dir| !dir.is_empty()) {
+        return Ok(Paths::new(dir));
+    }
+
+    let portable_flag = args.iter().any(|arg| arg == "--portable");
+    if let Some(exe_dir) = &exe_dir {
+        if portable_flag || exe_dir.join(PORTABLE_MARKER).exists() {
+            return Ok(Paths::new(exe_dir.join(PORTABLE_DATA_DIR)));
+        }
+    }
+    if portable_flag {
+        return Err(Error::Config("não foi possível localizar o executável para o modo portátil".to_string()));
+    }
+
+    system_dir
+        .map(|dir| Paths::new(dir.join(APP_DIR_NAME)))
+        .ok_or(Error::NoDataDir)
+}
+
+/// Lê `--data-dir <pasta>` ou `--data-dir=<pasta>`
+fn data_dir_arg(args: &[OsString]) -> Result<Option<PathBuf>, Error> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = if arg == "--data-dir" {
+            args.next().cloned()
+        } else if let Some(value) = arg.to_str().and_then(|arg| arg.strip_prefix("--data-dir=")) {
+            Some(OsString::from(value))
+        } else {
+            continue;
+        };
+
+        return match value.filter(|value| !value.is_empty()) {
+            Some(value) => Ok(Some(PathBuf::from(value))),
+            None => Err(Error::Config("--data-dir precisa de uma pasta".to_string())),
+        };
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<OsString> {
+        values.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn test_resolve_precedence() {
+        let exe_dir = std::env::temp_dir().join(format!("feather-alloy-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&exe_dir).unwrap();
+        let system = Some(PathBuf::from("/home/user/.local/share"));
+        let env = Some(OsString::from("/srv/env"));
+        let resolve_with = |values: &[&str], env: Option<OsString>| {
+            resolve(&args(values), env, Some(exe_dir.clone()), system.clone()).map(|paths| paths.data_dir)
+        };
+
+        assert_eq!(resolve_with(&[], None).unwrap(), PathBuf::from("/home/user/.local/share/feather-alloy"));
+        assert_eq!(resolve_with(&[], env.clone()).unwrap(), PathBuf::from("/srv/env"));
+        assert_eq!(resolve_with(&["--portable"], None).unwrap(), exe_dir.join("data"));
+        assert_eq!(resolve_with(&["--data-dir", "/srv/cli"], env.clone()).unwrap(), PathBuf::from("/srv/cli"));
+        assert_eq!(resolve_with(&["--data-dir=/srv/cli", "--portable"], env.clone()).unwrap(), PathBuf::from("/srv/cli"));
+        assert!(matches!(resolve_with(&["--data-dir"], None), Err(Error::Config(_))));
+        assert!(matches!(resolve(&[], None, None, None), Err(Error::NoDataDir)));
+
+        // O arquivo marcador ativa o modo portátil sem precisar da flag
+        std::fs::write(exe_dir.join(PORTABLE_MARKER), b"").unwrap();
+        assert_eq!(resolve_with(&[], None).unwrap(), exe_dir.join("data"));
+        assert_eq!(resolve_with(&[], env).unwrap(), PathBuf::from("/srv/env"));
+
+        std::fs::remove_dir_all(&exe_dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_locations() {
+        let paths = Paths::new("/data");
+        assert_eq!(paths.profiles_file(), PathBuf::from("/data/profiles.json"));
+        assert_eq!(paths.settings_file(), PathBuf::from("/data/settings.json"));
+        assert_eq!(paths.workspaces_file(), PathBuf::from("/data/workspaces.json"));
+        assert_eq!(paths.profile_dir("abc"), PathBuf::from("/data/profiles/abc"));
+        assert_eq!(paths.logs_dir(), PathBuf::from("/data/logs"));
+        assert_eq!(paths.instance_socket(), PathBuf::from("/data/instance.sock"));
+    }
+}
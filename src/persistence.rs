@@ -1,114 +1,226 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
-use crate::profile::{WebProfile, AppSettings};
-
-/// Retorna o caminho do arquivo de configuração de perfis
-pub fn get_profiles_file_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let config_dir = dirs::data_dir()
-        .ok_or("Failed to get data directory")?
-        .join("feather-alloy");
-    
-    // Criar diretório se não existir
-    fs::create_dir_all(&config_dir)?;
-    
-    Ok(config_dir.join("profiles.json"))
+use std::io::{Read, Seek, Write};
+use std::path::{Component, Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::migrations::ConfigKind;
+use crate::Error;
+use crate::paths::Paths;
+use crate::profile::{WebProfile, AppSettings, Workspaces};
+
+/// Quantidade de cópias `.bak.N` mantidas para cada arquivo de configuração
+const MAX_BACKUPS: usize = 3;
+
+fn backup_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".bak.{}", index));
+    PathBuf::from(name)
+}
+
+/// Grava o arquivo de forma atômica: escreve em um temporário, faz fsync e
+/// renomeia sobre o original. Antes disso, o conteúdo atual vira `.bak.1`
+/// (e os backups anteriores são deslocados até `MAX_BACKUPS`).
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), Error> {
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+    }
+
+    if path.exists() {
+        for index in (1..MAX_BACKUPS).rev() {
+            let from = backup_path(path, index);
+            if from.exists() {
+                fs::rename(&from, backup_path(path, index + 1))?;
+            }
+        }
+        // Cópia (e não rename) para que o arquivo original nunca deixe de existir
+        fs::copy(path, backup_path(path, 1))?;
+    }
+
+    fs::rename(&tmp_path, path)?;
+
+    // Garantir que o rename chegou ao disco
+    #[cfg(unix)]
+    if let Some(dir) = path.parent() {
+        fs::File::open(dir)?.sync_all()?;
+    }
+
+    Ok(())
+}
+
+/// Lê e interpreta um arquivo de configuração, recuperando de backups.
+///
+/// Retorna `None` se o arquivo não existir. Se o arquivo estiver corrompido,
+/// ele é renomeado para `.corrupt-<timestamp>` e o backup válido mais recente
+/// é restaurado. Só retorna `None` nesse caso se nenhum backup for válido.
+/// Erros de leitura (permissão, disco) são repassados sem mexer no arquivo.
+pub fn read_with_recovery<T>(
+    path: &Path,
+    parse: impl Fn(&str) -> Result<T, Error>,
+) -> Result<Option<T>, Error> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    // UTF-8 inválido conta como corrupção, não como falha de leitura
+    let bytes = fs::read(path)?;
+    let error = match String::from_utf8(bytes) {
+        Ok(json) => match parse(&json) {
+            Ok(value) => return Ok(Some(value)),
+            Err(e) => e,
+        },
+        Err(e) => Error::Config(e.to_string()),
+    };
+    log::warn!("Failed to parse {:?}: {}", path, error);
+
+    // Manter o arquivo corrompido de lado para inspeção manual
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let mut corrupt_name = path.as_os_str().to_owned();
+    corrupt_name.push(format!(".corrupt-{}", timestamp));
+    let corrupt_path = PathBuf::from(corrupt_name);
+    fs::rename(path, &corrupt_path)?;
+    log::warn!("Corrupt file moved to {:?}", corrupt_path);
+
+    for index in 1..=MAX_BACKUPS {
+        let backup = backup_path(path, index);
+        let Ok(json) = fs::read_to_string(&backup) else {
+            continue;
+        };
+        match parse(&json) {
+            Ok(value) => {
+                fs::copy(&backup, path)?;
+                log::info!("Restored {:?} from backup {:?}", path, backup);
+                return Ok(Some(value));
+            }
+            Err(e) => log::warn!("Backup {:?} is also invalid: {}", backup, e),
+        }
+    }
+
+    log::warn!("No valid backup found for {:?}", path);
+    Ok(None)
+}
+
+/// Arquivo gravado por uma versão mais nova do aplicativo: guarda uma cópia
+/// `<arquivo>.v<N>` antes que a próxima gravação o reescreva no formato atual,
+/// descartando os campos que esta versão não conhece
+fn preserve_newer_version(path: &Path) -> Result<(), Error> {
+    let Ok(json) = fs::read_to_string(path) else {
+        return Ok(());
+    };
+    let Some(version) = crate::migrations::newer_version(&json) else {
+        return Ok(());
+    };
+
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".v{}", version));
+    let copy = PathBuf::from(name);
+    if !copy.exists() {
+        fs::copy(path, &copy)?;
+        log::warn!("{:?} was written by a newer version, original kept at {:?}", path, copy);
+    }
+    Ok(())
 }
 
 /// Salva a lista de perfis em arquivo JSON
-pub fn save_profiles(profiles: &[WebProfile]) -> Result<(), Box<dyn std::error::Error>> {
-    let file_path = get_profiles_file_path()?;
-    let json = serde_json::to_string_pretty(profiles)?;
+pub fn save_profiles(paths: &Paths, profiles: &[WebProfile]) -> Result<(), Error> {
+    fs::create_dir_all(paths.data_dir())?;
+    let file_path = paths.profiles_file();
+    let json = crate::migrations::to_versioned_json(profiles)?;
     
-    fs::write(&file_path, json)?;
-    println!("[Persistence] Profiles saved to: {:?}", file_path);
+    write_atomic(&file_path, json.as_bytes())?;
+    log::debug!("Profiles saved to: {:?}", file_path);
     
     Ok(())
 }
 
 /// Carrega a lista de perfis do arquivo JSON
-pub fn load_profiles() -> Result<Vec<WebProfile>, Box<dyn std::error::Error>> {
-    let file_path = get_profiles_file_path()?;
-    
-    // Se o arquivo não existir, retornar lista vazia
-    if !file_path.exists() {
-        println!("[Persistence] No profiles file found, starting with empty list");
-        return Ok(Vec::new());
-    }
+pub fn load_profiles(paths: &Paths) -> Result<Vec<WebProfile>, Error> {
+    let file_path = paths.profiles_file();
+    preserve_newer_version(&file_path)?;
     
-    let json = fs::read_to_string(&file_path)?;
-    
-    // Tentar fazer parse do JSON
-    match serde_json::from_str::<Vec<WebProfile>>(&json) {
-        Ok(profiles) => {
-            println!("[Persistence] Loaded {} profiles from: {:?}", 
+    match read_with_recovery(&file_path, |json| crate::migrations::from_versioned_json::<Vec<WebProfile>>(ConfigKind::Profiles, json))? {
+        Some(profiles) => {
+            log::info!("Loaded {} profiles from: {:?}", 
                      profiles.len(), file_path);
             Ok(profiles)
         }
-        Err(e) => {
-            eprintln!("[Persistence] Failed to parse profiles file: {}", e);
-            eprintln!("[Persistence] Starting with empty list");
+        None => {
+            log::info!("No profiles file found, starting with empty list");
             Ok(Vec::new())
         }
     }
 }
 
-/// Retorna o caminho do arquivo de configurações
-pub fn get_settings_file_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let config_dir = dirs::data_dir()
-        .ok_or("Failed to get data directory")?
-        .join("feather-alloy");
-    
-    // Criar diretório se não existir
-    fs::create_dir_all(&config_dir)?;
-    
-    Ok(config_dir.join("settings.json"))
-}
-
 /// Salva as configurações em arquivo JSON
-pub fn save_settings(settings: &AppSettings) -> Result<(), Box<dyn std::error::Error>> {
-    let file_path = get_settings_file_path()?;
-    let json = serde_json::to_string_pretty(settings)?;
+pub fn save_settings(paths: &Paths, settings: &AppSettings) -> Result<(), Error> {
+    fs::create_dir_all(paths.data_dir())?;
+    let file_path = paths.settings_file();
+    let json = crate::migrations::to_versioned_json(settings)?;
     
-    fs::write(&file_path, json)?;
-    println!("[Persistence] Settings saved to: {:?}", file_path);
+    write_atomic(&file_path, json.as_bytes())?;
+    log::debug!("Settings saved to: {:?}", file_path);
     
     Ok(())
 }
 
 /// Carrega as configurações do arquivo JSON
-pub fn load_settings() -> Result<AppSettings, Box<dyn std::error::Error>> {
-    let file_path = get_settings_file_path()?;
+pub fn load_settings(paths: &Paths) -> Result<AppSettings, Error> {
+    let file_path = paths.settings_file();
+    preserve_newer_version(&file_path)?;
     
-    if !file_path.exists() {
-        println!("[Persistence] No settings file found, using defaults");
-        return Ok(AppSettings::default());
-    }
-    
-    let json = fs::read_to_string(&file_path)?;
-    
-    match serde_json::from_str::<AppSettings>(&json) {
-        Ok(settings) => {
-            println!("[Persistence] Loaded settings from: {:?}", file_path);
+    match read_with_recovery(&file_path, |json| crate::migrations::from_versioned_json::<AppSettings>(ConfigKind::Settings, json))? {
+        Some(settings) => {
+            log::info!("Loaded settings from: {:?}", file_path);
             Ok(settings)
         }
-        Err(e) => {
-            eprintln!("[Persistence] Failed to parse settings file: {}, using defaults", e);
+        None => {
+            log::info!("No settings file found, using defaults");
             Ok(AppSettings::default())
         }
     }
 }
 
+/// Salva os workspaces e o workspace selecionado
+pub fn save_workspaces(paths: &Paths, workspaces: &Workspaces) -> Result<(), Error> {
+    fs::create_dir_all(paths.data_dir())?;
+    let file_path = paths.workspaces_file();
+    let json = crate::migrations::to_versioned_json(workspaces)?;
+    
+    write_atomic(&file_path, json.as_bytes())?;
+    log::debug!("Workspaces saved to: {:?}", file_path);
+    
+    Ok(())
+}
+
+/// Carrega os workspaces (nenhum, se o arquivo ainda não existir)
+pub fn load_workspaces(paths: &Paths) -> Result<Workspaces, Error> {
+    let file_path = paths.workspaces_file();
+    preserve_newer_version(&file_path)?;
+    
+    match read_with_recovery(&file_path, |json| crate::migrations::from_versioned_json::<Workspaces>(ConfigKind::Workspaces, json))? {
+        Some(workspaces) => {
+            log::info!("Loaded {} workspaces from: {:?}", workspaces.workspaces.len(), file_path);
+            Ok(workspaces)
+        }
+        None => Ok(Workspaces::default()),
+    }
+}
+
 /// Deleta os dados de um perfil do disco
-pub fn delete_profile_data(uuid: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let profile_dir = dirs::data_dir()
-        .ok_or("Failed to get data directory")?
-        .join("feather-alloy")
-        .join("profiles")
-        .join(uuid);
+pub fn delete_profile_data(paths: &Paths, uuid: &str) -> Result<(), Error> {
+    let profile_dir = paths.profile_dir(uuid);
     
     if profile_dir.exists() {
         fs::remove_dir_all(&profile_dir)?;
-        println!("[Persistence] Deleted profile data: {:?}", profile_dir);
+        log::info!("Deleted profile data: {:?}", profile_dir);
     }
     
     Ok(())
@@ -116,21 +228,20 @@ pub fn delete_profile_data(uuid: &str) -> Result<(), Box<dyn std::error::Error>>
 
 
 /// Salva o ícone do perfil na pasta de dados do perfil
-pub fn save_profile_icon(uuid: &str, source_path: &str) -> Result<String, Box<dyn std::error::Error>> {
+pub fn save_profile_icon(paths: &Paths, uuid: &str, source_path: &str) -> Result<String, Error> {
     let source = PathBuf::from(source_path);
     if !source.exists() {
-        return Err(format!("Source icon not found: {}", source_path).into());
+        return Err(Error::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("ícone não encontrado: {}", source_path),
+        )));
     }
 
     let extension = source.extension()
         .and_then(|e| e.to_str())
         .unwrap_or("png");
     
-    let profile_dir = dirs::data_dir()
-        .ok_or("Failed to get data directory")?
-        .join("feather-alloy")
-        .join("profiles")
-        .join(uuid);
+    let profile_dir = paths.profile_dir(uuid);
 
     // Ensure dir exists
     fs::create_dir_all(&profile_dir)?;
@@ -139,7 +250,7 @@ pub fn save_profile_icon(uuid: &str, source_path: &str) -> Result<String, Box<dy
     let dest_path = profile_dir.join(&dest_filename);
 
     fs::copy(&source, &dest_path)?;
-    println!("[Persistence] Icon copied to: {:?}", dest_path);
+    log::debug!("Icon copied to: {:?}", dest_path);
 
     // Return relative path: profiles/{uuid}/{filename}
     let relative_path = format!("profiles/{}/{}", uuid, dest_filename);
@@ -147,12 +258,8 @@ pub fn save_profile_icon(uuid: &str, source_path: &str) -> Result<String, Box<dy
 }
 
 /// Remove o ícone do perfil se existir
-pub fn delete_profile_icon(uuid: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let profile_dir = dirs::data_dir()
-        .ok_or("Failed to get data directory")?
-        .join("feather-alloy")
-        .join("profiles")
-        .join(uuid);
+pub fn delete_profile_icon(paths: &Paths, uuid: &str) -> Result<(), Error> {
+    let profile_dir = paths.profile_dir(uuid);
 
     if !profile_dir.exists() {
         return Ok(());
@@ -165,7 +272,7 @@ pub fn delete_profile_icon(uuid: &str) -> Result<(), Box<dyn std::error::Error>>
         if let Some(stem) = path.file_stem() {
             if stem == "icon" {
                 fs::remove_file(&path)?;
-                println!("[Persistence] Deleted icon: {:?}", path);
+                log::debug!("Deleted icon: {:?}", path);
             }
         }
     }
@@ -179,6 +286,246 @@ pub fn delete_profile_icon(uuid: &str) -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
+/// Como aplicar um arquivo de backup importado
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportMode {
+    /// Adiciona os perfis importados aos atuais e mantém as configurações
+    Merge,
+    /// Substitui todos os perfis e as configurações
+    Replace,
+}
+
+/// Resultado de uma importação (os arquivos dos perfis já foram extraídos)
+#[derive(Debug)]
+pub struct ImportResult {
+    /// Perfis importados, já com os UUIDs finais
+    pub profiles: Vec<WebProfile>,
+    /// Configurações do arquivo (apenas no modo `Replace`)
+    pub settings: Option<AppSettings>,
+    /// Workspaces do arquivo (apenas no modo `Replace`; vazio em backups sem workspaces)
+    pub workspaces: Option<Workspaces>,
+    /// Quantos perfis receberam um novo UUID por conflito
+    pub remapped: usize,
+}
+
+/// Exporta perfis, workspaces, configurações e ícones para um arquivo zip.
+/// Com `include_sessions`, inclui também os dados de sessão de `profiles/{uuid}/`.
+pub fn export_archive(
+    paths: &Paths,
+    dest: &Path,
+    profiles: &[WebProfile],
+    workspaces: &Workspaces,
+    settings: &AppSettings,
+    include_sessions: bool,
+) -> Result<(), Error> {
+    write_archive(paths.data_dir(), dest, profiles, workspaces, settings, include_sessions)?;
+    log::info!("Exported {} profiles to: {:?}", profiles.len(), dest);
+    Ok(())
+}
+
+/// Importa um arquivo gerado por `export_archive`. UUIDs que já existem em
+/// `existing` são substituídos por novos, inclusive no modo `Replace` (os
+/// perfis atuais só devem ser removidos depois que a importação der certo).
+pub fn import_archive(
+    paths: &Paths,
+    src: &Path,
+    existing: &[WebProfile],
+    mode: ImportMode,
+) -> Result<ImportResult, Error> {
+    let result = read_archive(paths.data_dir(), src, existing, mode)?;
+    log::info!(
+        "Imported {} profiles from {:?} ({} remapped)",
+        result.profiles.len(), src, result.remapped
+    );
+    Ok(result)
+}
+
+fn write_archive(
+    data_dir: &Path,
+    dest: &Path,
+    profiles: &[WebProfile],
+    workspaces: &Workspaces,
+    settings: &AppSettings,
+    include_sessions: bool,
+) -> Result<(), Error> {
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let mut zip = zip::ZipWriter::new(fs::File::create(dest)?);
+
+    let mut exported = profiles.to_vec();
+    for profile in &mut exported {
+        let profile_dir = data_dir.join("profiles").join(&profile.uuid);
+        let prefix = format!("profiles/{}", profile.uuid);
+
+        if include_sessions {
+            add_dir_to_zip(&mut zip, &profile_dir, &prefix, options)?;
+        } else if profile_dir.is_dir() {
+            // Apenas ícones (icon.* e favicon.*)
+            for entry in fs::read_dir(&profile_dir)? {
+                let path = entry?.path();
+                let is_icon = path.is_file()
+                    && path.file_stem().is_some_and(|stem| stem == "icon" || stem == "favicon");
+                if is_icon {
+                    let name = format!("{}/{}", prefix, path.file_name().unwrap_or_default().to_string_lossy());
+                    zip.start_file(name, options)?;
+                    std::io::copy(&mut fs::File::open(&path)?, &mut zip)?;
+                }
+            }
+        }
+
+        // Ícone fora da pasta de dados: levar uma cópia no arquivo
+        let external_icon = profile
+            .icon_path
+            .as_ref()
+            .map(PathBuf::from)
+            .filter(|icon| icon.is_absolute() && icon.is_file() && !icon.starts_with(&profile_dir));
+        if let Some(icon) = external_icon {
+            let extension = icon.extension().and_then(|e| e.to_str()).unwrap_or("png");
+            let name = format!("{}/icon.{}", prefix, extension);
+            if !profile_dir.join(format!("icon.{}", extension)).exists() {
+                zip.start_file(name.as_str(), options)?;
+                std::io::copy(&mut fs::File::open(&icon)?, &mut zip)?;
+            }
+            profile.icon_path = Some(name);
+        }
+    }
+
+    zip.start_file("profiles.json", options)?;
+    zip.write_all(crate::migrations::to_versioned_json(&exported)?.as_bytes())?;
+    zip.start_file("workspaces.json", options)?;
+    zip.write_all(crate::migrations::to_versioned_json(workspaces)?.as_bytes())?;
+    zip.start_file("settings.json", options)?;
+    zip.write_all(crate::migrations::to_versioned_json(settings)?.as_bytes())?;
+    zip.finish()?;
+
+    Ok(())
+}
+
+fn add_dir_to_zip<W: Write + Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    dir: &Path,
+    prefix: &str,
+    options: zip::write::SimpleFileOptions,
+) -> Result<(), Error> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = format!("{}/{}", prefix, path.file_name().unwrap_or_default().to_string_lossy());
+        if path.is_dir() {
+            add_dir_to_zip(zip, &path, &name, options)?;
+        } else if path.is_file() {
+            zip.start_file(name, options)?;
+            std::io::copy(&mut fs::File::open(&path)?, zip)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_archive(
+    data_dir: &Path,
+    src: &Path,
+    existing: &[WebProfile],
+    mode: ImportMode,
+) -> Result<ImportResult, Error> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(src)?)?;
+
+    let mut profiles: Vec<WebProfile> =
+        crate::migrations::from_versioned_json(ConfigKind::Profiles, &read_zip_entry(&mut archive, "profiles.json")?)?;
+    let settings = match mode {
+        ImportMode::Replace => Some(crate::migrations::from_versioned_json(
+            ConfigKind::Settings,
+            &read_zip_entry(&mut archive, "settings.json")?,
+        )?),
+        ImportMode::Merge => None,
+    };
+    // Backups anteriores aos workspaces não têm workspaces.json
+    let workspaces = match mode {
+        ImportMode::Replace if archive.file_names().any(|name| name == "workspaces.json") => {
+            Some(crate::migrations::from_versioned_json(
+                ConfigKind::Workspaces,
+                &read_zip_entry(&mut archive, "workspaces.json")?,
+            )?)
+        }
+        ImportMode::Replace => Some(Workspaces::default()),
+        ImportMode::Merge => None,
+    };
+
+    // UUID antigo -> UUID final. UUIDs inválidos também são trocados, já que
+    // viram nomes de diretório
+    let mut taken: HashSet<String> = existing.iter().map(|p| p.uuid.clone()).collect();
+    let mut uuid_map = HashMap::new();
+    let mut remapped = 0;
+    for profile in &mut profiles {
+        let old_uuid = profile.uuid.clone();
+        if taken.contains(&old_uuid) || uuid::Uuid::parse_str(&old_uuid).is_err() {
+            profile.uuid = uuid::Uuid::new_v4().to_string();
+            remapped += 1;
+        }
+        taken.insert(profile.uuid.clone());
+
+        let old_prefix = format!("profiles/{}/", old_uuid);
+        let icon_path = profile
+            .icon_path
+            .as_deref()
+            .and_then(|icon| icon.strip_prefix(&old_prefix))
+            .map(|file| format!("profiles/{}/{}", profile.uuid, file));
+        if icon_path.is_some() {
+            profile.icon_path = icon_path;
+        }
+        uuid_map.insert(old_uuid, profile.uuid.clone());
+    }
+
+    // Extrair profiles/{uuid}/... para o diretório do UUID final
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let Some(path) = entry.enclosed_name() else {
+            continue;
+        };
+        let mut components = path.components();
+        if components.next() != Some(Component::Normal("profiles".as_ref())) {
+            continue;
+        }
+        let Some(Component::Normal(old_uuid)) = components.next() else {
+            continue;
+        };
+        let Some(new_uuid) = uuid_map.get(old_uuid.to_string_lossy().as_ref()) else {
+            continue;
+        };
+        let relative = components.as_path();
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+
+        let target = data_dir.join("profiles").join(new_uuid).join(relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        std::io::copy(&mut entry, &mut fs::File::create(&target)?)?;
+    }
+
+    Ok(ImportResult { profiles, settings, workspaces, remapped })
+}
+
+fn read_zip_entry<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<String, Error> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|_| Error::Archive(format!("o arquivo não contém {}", name)))?;
+    let mut contents = String::new();
+    entry.read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,15 +540,175 @@ mod tests {
         );
         
         let profiles = vec![test_profile];
+        let dir = temp_dir();
+        let paths = Paths::new(dir.join("data"));
         
         // Salvar
-        save_profiles(&profiles).expect("Failed to save profiles");
+        save_profiles(&paths, &profiles).expect("Failed to save profiles");
         
         // Carregar
-        let loaded = load_profiles().expect("Failed to load profiles");
+        let loaded = load_profiles(&paths).expect("Failed to load profiles");
         
         assert_eq!(loaded.len(), 1);
         assert_eq!(loaded[0].name, "Test Service");
         assert_eq!(loaded[0].url, "https://example.com");
+        assert!(dir.join("data/profiles.json").exists());
+
+        // Sem arquivo, nenhum workspace
+        assert_eq!(load_workspaces(&paths).unwrap(), Workspaces::default());
+        let workspaces = Workspaces {
+            workspaces: vec![crate::profile::Workspace::new("Trabalho".to_string())],
+            active: None,
+        };
+        save_workspaces(&paths, &workspaces).unwrap();
+        assert_eq!(load_workspaces(&paths).unwrap(), workspaces);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("feather-alloy-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_newer_version_is_preserved() {
+        let dir = temp_dir();
+        let paths = Paths::new(&dir);
+        let newer = r#"{ "version": 99, "data": { "enable_tray": false, "new_option": true } }"#;
+        fs::write(paths.settings_file(), newer).unwrap();
+
+        let settings = load_settings(&paths).unwrap();
+        assert!(!settings.enable_tray);
+        save_settings(&paths, &settings).unwrap();
+        load_settings(&paths).unwrap();
+
+        // A cópia continua com os campos desconhecidos, mesmo depois de regravar
+        let copy = dir.join("settings.json.v99");
+        assert_eq!(fs::read_to_string(copy).unwrap(), newer);
+        assert!(!fs::read_to_string(paths.settings_file()).unwrap().contains("new_option"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_atomic_rotates_backups() {
+        let dir = temp_dir();
+        let path = dir.join("settings.json");
+
+        for version in 1..=5 {
+            write_atomic(&path, version.to_string().as_bytes()).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "5");
+        assert_eq!(fs::read_to_string(backup_path(&path, 1)).unwrap(), "4");
+        assert_eq!(fs::read_to_string(backup_path(&path, 3)).unwrap(), "2");
+        assert!(!backup_path(&path, 4).exists());
+        assert!(!dir.join("settings.json.tmp").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_with_recovery_restores_backup() {
+        let dir = temp_dir();
+        let path = dir.join("profiles.json");
+        let parse = |json: &str| -> Result<Vec<u32>, Error> { Ok(serde_json::from_str(json)?) };
+
+        assert!(read_with_recovery(&path, parse).unwrap().is_none());
+
+        write_atomic(&path, b"[1]").unwrap();
+        write_atomic(&path, b"[1, 2]").unwrap();
+        write_atomic(&path, b"[1, 2, 3]").unwrap();
+        fs::write(backup_path(&path, 1), b"{").unwrap();
+        // Simula uma gravação interrompida
+        fs::write(&path, b"[1, 2, 3, 4").unwrap();
+
+        // O backup mais recente também está inválido, então usa o seguinte
+        assert_eq!(read_with_recovery(&path, parse).unwrap(), Some(vec![1]));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[1]");
+
+        let corrupt_files = fs::read_dir(&dir)
+            .unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().contains(".corrupt-"))
+            .count();
+        assert_eq!(corrupt_files, 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_with_recovery_keeps_file_on_read_error() {
+        let dir = temp_dir();
+        let path = dir.join("settings.json");
+        let parse = |json: &str| -> Result<Vec<u32>, Error> { Ok(serde_json::from_str(json)?) };
+
+        // Um diretório no lugar do arquivo faz a leitura falhar antes do parse
+        fs::create_dir(&path).unwrap();
+        fs::write(backup_path(&path, 1), b"[1]").unwrap();
+
+        assert!(matches!(read_with_recovery(&path, parse), Err(Error::Io(_))));
+        assert!(path.is_dir());
+        let corrupt_files = fs::read_dir(&dir)
+            .unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().contains(".corrupt-"))
+            .count();
+        assert_eq!(corrupt_files, 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_export_and_import_archive() {
+        let source_dir = temp_dir();
+        let profile = WebProfile::new("Gmail".to_string(), "https://mail.google.com".to_string(), None, None);
+        let uuid = profile.uuid.clone();
+        let profile_dir = source_dir.join("profiles").join(&uuid);
+        fs::create_dir_all(profile_dir.join("storage")).unwrap();
+        fs::write(profile_dir.join("icon.png"), b"icon").unwrap();
+        fs::write(profile_dir.join("storage").join("cookies.db"), b"session").unwrap();
+        let workspaces = Workspaces {
+            workspaces: vec![crate::profile::Workspace::new("Trabalho".to_string())],
+            active: None,
+        };
+        let mut exported = profile.clone();
+        exported.icon_path = Some(format!("profiles/{}/icon.png", uuid));
+        exported.workspaces = vec![workspaces.workspaces[0].uuid.clone()];
+
+        let settings = AppSettings {
+            hibernate_after_minutes: 5,
+            ..AppSettings::default()
+        };
+
+        let archive = source_dir.join("backup.zip");
+        let archive_with_sessions = source_dir.join("backup-sessions.zip");
+        write_archive(&source_dir, &archive, &[exported.clone()], &workspaces, &settings, false).unwrap();
+        write_archive(&source_dir, &archive_with_sessions, &[exported.clone()], &workspaces, &settings, true).unwrap();
+
+        // Merge com um perfil de mesmo UUID já existente: recebe um novo UUID
+        let target_dir = temp_dir();
+        let result = read_archive(&target_dir, &archive, &[exported.clone()], ImportMode::Merge).unwrap();
+        assert_eq!(result.remapped, 1);
+        assert!(result.settings.is_none());
+        assert!(result.workspaces.is_none());
+        let imported = &result.profiles[0];
+        assert_ne!(imported.uuid, uuid);
+        assert_eq!(imported.icon_path, Some(format!("profiles/{}/icon.png", imported.uuid)));
+        let imported_dir = target_dir.join("profiles").join(&imported.uuid);
+        assert_eq!(fs::read(imported_dir.join("icon.png")).unwrap(), b"icon");
+        assert!(!imported_dir.join("storage").exists());
+
+        // Replace sem conflitos mantém o UUID e traz as configurações e sessões
+        let result = read_archive(&target_dir, &archive_with_sessions, &[], ImportMode::Replace).unwrap();
+        assert_eq!(result.remapped, 0);
+        assert_eq!(result.profiles[0].uuid, uuid);
+        assert_eq!(result.settings.unwrap().hibernate_after_minutes, 5);
+        assert_eq!(result.workspaces, Some(workspaces));
+        let session_file = target_dir.join("profiles").join(&uuid).join("storage").join("cookies.db");
+        assert_eq!(fs::read(session_file).unwrap(), b"session");
+
+        fs::remove_dir_all(&source_dir).unwrap();
+        fs::remove_dir_all(&target_dir).unwrap();
     }
 }
@@ -7,11 +7,31 @@ pub struct WebProfile {
     pub uuid: String,
     pub name: String,
     pub url: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub icon_path: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub user_agent: Option<String>,
+    #[serde(default = "default_true")]
     pub auto_hibernate: bool,
+    /// Regexes para extrair não lidas do título (vazio = padrões padrão)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unread_patterns: Vec<String>,
+    /// Não exibir notificações de desktop deste perfil
+    #[serde(default)]
+    pub notifications_muted: bool,
+    /// Domínios extras que abrem dentro do perfil em vez do navegador
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_domains: Vec<String>,
+    /// Perfis desativados continuam na barra lateral, mas não carregam a webview
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// UUIDs dos workspaces em que o perfil aparece
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workspaces: Vec<String>,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl WebProfile {
@@ -28,16 +48,64 @@ impl WebProfile {
             icon_path,
             user_agent,
             auto_hibernate: true,
+            unread_patterns: Vec::new(),
+            notifications_muted: false,
+            allowed_domains: Vec::new(),
+            enabled: true,
+            workspaces: Vec::new(),
         }
     }
 }
 
+/// Campos editáveis de um perfil, como enviados pelo formulário de edição
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileUpdate {
+    pub name: String,
+    pub url: String,
+    pub icon_path: Option<String>,
+    pub user_agent: Option<String>,
+    pub auto_hibernate: bool,
+    #[serde(default)]
+    pub unread_patterns: Vec<String>,
+    #[serde(default)]
+    pub notifications_muted: bool,
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub workspaces: Vec<String>,
+}
+
+/// Descarta User-Agents vazios ou só com espaços (equivale a usar o padrão)
+pub fn normalize_user_agent(user_agent: Option<String>) -> Option<String> {
+    user_agent
+        .map(|ua| ua.trim().to_string())
+        .filter(|ua| !ua.is_empty())
+}
+
+/// Campos ausentes (arquivos antigos) usam os valores de `AppSettings::default()`
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppSettings {
     pub minimize_on_open: bool,
     pub minimize_on_close: bool,
     pub hide_on_close: bool,
     pub enable_tray: bool,
+    /// Minutos sem uso até hibernar webviews em background (0 = nunca)
+    pub hibernate_after_minutes: u64,
+    /// Pasta de downloads (None = pasta de downloads do sistema)
+    pub download_dir: Option<String>,
+    /// Salvar os downloads de cada perfil em uma subpasta com o nome do perfil
+    pub downloads_per_profile: bool,
+    /// Registrar mensagens de depuração no log
+    pub verbose_logging: bool,
+    /// Hibernar os perfis que ficam fora do workspace selecionado
+    pub hibernate_inactive_workspaces: bool,
+    /// Atalhos de teclado
+    pub shortcuts: crate::shortcuts::Shortcuts,
+    /// Atalho do sistema para mostrar/ocultar a janela (vazio = desativado; só no X11)
+    pub global_hotkey: String,
 }
 
 impl Default for AppSettings {
@@ -47,13 +115,78 @@ impl Default for AppSettings {
             minimize_on_close: false,
             hide_on_close: true,
             enable_tray: true,
+            hibernate_after_minutes: 30,
+            download_dir: None,
+            downloads_per_profile: false,
+            verbose_logging: false,
+            hibernate_inactive_workspaces: false,
+            shortcuts: crate::shortcuts::Shortcuts::default(),
+            global_hotkey: String::new(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// Limpa e valida valores vindos do formulário de configurações
+    pub fn normalized(mut self) -> Result<Self, crate::Error> {
+        self.download_dir = self
+            .download_dir
+            .map(|dir| dir.trim().to_string())
+            .filter(|dir| !dir.is_empty());
+        self.shortcuts = self.shortcuts.normalized()?;
+        if self.global_hotkey.trim().is_empty() {
+            self.global_hotkey.clear();
+        } else {
+            self.global_hotkey = crate::shortcuts::normalize_combo(&self.global_hotkey)
+                .ok_or_else(|| crate::Error::Config(format!("atalho global inválido: {}", self.global_hotkey.trim())))?;
+        }
+        Ok(self)
+    }
+}
+
+/// Grupo de perfis exibido junto na barra lateral
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub uuid: String,
+    pub name: String,
+}
+
+impl Workspace {
+    pub fn new(name: String) -> Self {
+        Self {
+            uuid: uuid::Uuid::new_v4().to_string(),
+            name,
+        }
+    }
+}
+
+/// Workspaces cadastrados e o selecionado na barra lateral
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Workspaces {
+    pub workspaces: Vec<Workspace>,
+    /// Workspace exibido (None = todos os perfis)
+    pub active: Option<String>,
+}
+
+impl Workspaces {
+    pub fn exists(&self, uuid: &str) -> bool {
+        self.workspaces.iter().any(|w| w.uuid == uuid)
+    }
+
+    /// O perfil aparece na barra lateral com o workspace atual?
+    pub fn shows(&self, profile: &WebProfile) -> bool {
+        match &self.active {
+            Some(active) => profile.workspaces.contains(active),
+            None => true,
         }
     }
 }
 
-/// Dados globais da aplicação (perfis e configurações)
+/// Dados globais da aplicação (perfis, workspaces e configurações)
 pub struct AppData {
     pub profiles: Vec<WebProfile>,
+    pub workspaces: Workspaces,
     pub settings: AppSettings,
 }
 
@@ -63,6 +196,7 @@ pub type AppState = Arc<Mutex<AppData>>;
 pub fn create_app_state() -> AppState {
     Arc::new(Mutex::new(AppData {
         profiles: Vec::new(),
+        workspaces: Workspaces::default(),
         settings: AppSettings::default(),
     }))
 }
@@ -0,0 +1,259 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// Modificadores na ordem usada pelo script das webviews ("Ctrl+Alt+Shift+Meta+Tecla")
+const MODIFIERS: [&str; 4] = ["Ctrl", "Alt", "Shift", "Meta"];
+
+/// Nomes de teclas aceitos, com a grafia de `KeyboardEvent.key`
+const NAMED_KEYS: [&str; 17] = [
+    "Tab", "Enter", "Escape", "Space", "Backspace", "Delete", "Insert", "Home", "End",
+    "PageUp", "PageDown", "ArrowUp", "ArrowDown", "ArrowLeft", "ArrowRight", "ContextMenu", "Pause",
+];
+
+/// Ação disparada por um atalho de teclado
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    /// N-ésimo perfil da barra lateral (0 = primeiro)
+    ActivateProfile(usize),
+    NextProfile,
+    PreviousProfile,
+    ReloadProfile,
+    OpenSettings,
+    OpenPalette,
+}
+
+/// Atalhos configuráveis, no formato "Ctrl+Shift+Tab". Vazio desativa o atalho.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Shortcuts {
+    /// Modificadores usados com os números 1 a 9 para abrir o N-ésimo perfil
+    pub profile_number: String,
+    pub next_profile: String,
+    pub previous_profile: String,
+    pub reload_profile: String,
+    pub open_settings: String,
+    pub command_palette: String,
+}
+
+impl Default for Shortcuts {
+    fn default() -> Self {
+        Self {
+            profile_number: "Ctrl".to_string(),
+            next_profile: "Ctrl+Tab".to_string(),
+            previous_profile: "Ctrl+Shift+Tab".to_string(),
+            reload_profile: "Ctrl+R".to_string(),
+            open_settings: "Ctrl+,".to_string(),
+            command_palette: "Ctrl+K".to_string(),
+        }
+    }
+}
+
+impl Shortcuts {
+    /// Valida os atalhos vindos do formulário e os deixa na forma canônica
+    pub fn normalized(self) -> Result<Self, Error> {
+        let combo = |value: String| -> Result<String, Error> {
+            if value.trim().is_empty() {
+                return Ok(String::new());
+            }
+            normalize_combo(&value).ok_or_else(|| Error::Config(format!("atalho inválido: {}", value.trim())))
+        };
+
+        let profile_number = if self.profile_number.trim().is_empty() {
+            String::new()
+        } else {
+            normalize_modifiers(&self.profile_number)
+                .ok_or_else(|| Error::Config(format!("modificadores inválidos: {}", self.profile_number.trim())))?
+        };
+        let shortcuts = Self {
+            profile_number,
+            next_profile: combo(self.next_profile)?,
+            previous_profile: combo(self.previous_profile)?,
+            reload_profile: combo(self.reload_profile)?,
+            open_settings: combo(self.open_settings)?,
+            command_palette: combo(self.command_palette)?,
+        };
+
+        let bindings = shortcuts.bindings();
+        if let Some(duplicate) = bindings.iter().enumerate().find(|(i, combo)| bindings[..*i].contains(*combo)) {
+            return Err(Error::Config(format!("o atalho {} está em uso por mais de uma ação", duplicate.1)));
+        }
+        Ok(shortcuts)
+    }
+
+    /// Todas as combinações ativas, para as webviews saberem quais teclas interceptar
+    pub fn bindings(&self) -> Vec<String> {
+        let mut bindings: Vec<String> = match normalize_modifiers(&self.profile_number) {
+            Some(modifiers) => (1..=9).map(|n| format!("{}+{}", modifiers, n)).collect(),
+            None => Vec::new(),
+        };
+        bindings.extend(
+            [&self.next_profile, &self.previous_profile, &self.reload_profile, &self.open_settings, &self.command_palette]
+                .into_iter()
+                .map(String::as_str)
+                .filter_map(normalize_combo),
+        );
+        bindings
+    }
+
+    /// Ação da combinação pressionada. Atalhos inválidos (ex.: arquivo editado à mão) são ignorados.
+    pub fn resolve(&self, combo: &str) -> Option<ShortcutAction> {
+        let combo = normalize_combo(combo)?;
+
+        if let Some((modifiers, digit)) = combo.rsplit_once('+') {
+            let number = digit.parse::<usize>().ok().filter(|n| (1..=9).contains(n));
+            if let Some(number) = number {
+                if normalize_modifiers(&self.profile_number).as_deref() == Some(modifiers) {
+                    return Some(ShortcutAction::ActivateProfile(number - 1));
+                }
+            }
+        }
+
+        [
+            (&self.next_profile, ShortcutAction::NextProfile),
+            (&self.previous_profile, ShortcutAction::PreviousProfile),
+            (&self.reload_profile, ShortcutAction::ReloadProfile),
+            (&self.open_settings, ShortcutAction::OpenSettings),
+            (&self.command_palette, ShortcutAction::OpenPalette),
+        ]
+        .into_iter()
+        .find(|(binding, _)| normalize_combo(binding).as_deref() == Some(combo.as_str()))
+        .map(|(_, action)| action)
+    }
+}
+
+fn modifier(name: &str) -> Option<&'static str> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Some("Ctrl"),
+        "alt" | "option" => Some("Alt"),
+        "shift" => Some("Shift"),
+        "meta" | "super" | "cmd" | "command" => Some("Meta"),
+        _ => None,
+    }
+}
+
+/// "shift+ctrl" -> "Ctrl+Shift"; precisa de ao menos um modificador
+fn normalize_modifiers(modifiers: &str) -> Option<String> {
+    let mut found = [false; MODIFIERS.len()];
+    for part in modifiers.split('+').filter(|part| !part.trim().is_empty()) {
+        let name = modifier(part)?;
+        found[MODIFIERS.iter().position(|m| *m == name)?] = true;
+    }
+    let names: Vec<&str> = MODIFIERS.iter().zip(found).filter(|(_, on)| *on).map(|(name, _)| *name).collect();
+    (!names.is_empty()).then(|| names.join("+"))
+}
+
+fn normalize_key(key: &str) -> Option<String> {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => return Some(c.to_uppercase().collect()),
+        (None, _) => return None,
+        _ => {}
+    }
+
+    if let Some(name) = NAMED_KEYS.iter().find(|name| name.eq_ignore_ascii_case(key)) {
+        return Some(name.to_string());
+    }
+    // Teclas de função F1 a F24
+    let number = key.strip_prefix(['F', 'f'])?.parse::<u8>().ok()?;
+    (1..=24).contains(&number).then(|| format!("F{}", number))
+}
+
+/// Forma canônica de uma combinação ("ctrl+shift+tab" -> "Ctrl+Shift+Tab"), igual
+/// à gerada pelo script das webviews. Sem modificador, só teclas de função.
+pub fn normalize_combo(combo: &str) -> Option<String> {
+    let combo = combo.trim();
+    // A própria tecla "+" fica no fim: "Ctrl++"
+    let (modifiers, key) = match combo.strip_suffix("++") {
+        Some(modifiers) => (modifiers, "+"),
+        None => combo.rsplit_once('+').unwrap_or(("", combo)),
+    };
+    let key = normalize_key(key.trim())?;
+
+    if modifiers.trim().is_empty() {
+        let function_key = key.len() > 1 && key.starts_with('F');
+        return function_key.then_some(key);
+    }
+    Some(format!("{}+{}", normalize_modifiers(modifiers)?, key))
+}
+
+/// Converte a combinação para o formato aceito por `global_hotkey::hotkey::HotKey`
+pub fn global_accelerator(combo: &str) -> Option<String> {
+    let combo = normalize_combo(combo)?;
+    let parts: Vec<&str> = combo.split('+').map(|part| if part == "Meta" { "Super" } else { part }).collect();
+    Some(parts.join("+"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_combo() {
+        assert_eq!(normalize_combo("ctrl+shift+tab").as_deref(), Some("Ctrl+Shift+Tab"));
+        assert_eq!(normalize_combo(" Shift + Control + r ").as_deref(), Some("Ctrl+Shift+R"));
+        assert_eq!(normalize_combo("Cmd+,").as_deref(), Some("Meta+,"));
+        assert_eq!(normalize_combo("Ctrl++").as_deref(), Some("Ctrl++"));
+        assert_eq!(normalize_combo("alt+pagedown").as_deref(), Some("Alt+PageDown"));
+        assert_eq!(normalize_combo("f5").as_deref(), Some("F5"));
+        // Teclas comuns sem modificador atrapalhariam a digitação
+        assert_eq!(normalize_combo("R"), None);
+        assert_eq!(normalize_combo("Ctrl+"), None);
+        assert_eq!(normalize_combo("Hyper+R"), None);
+        assert_eq!(normalize_combo("Ctrl+Banana"), None);
+    }
+
+    #[test]
+    fn test_global_accelerator() {
+        assert_eq!(global_accelerator("ctrl+alt+f").as_deref(), Some("Ctrl+Alt+F"));
+        assert_eq!(global_accelerator("Cmd+Shift+Space").as_deref(), Some("Shift+Super+Space"));
+        assert_eq!(global_accelerator("F12").as_deref(), Some("F12"));
+        assert_eq!(global_accelerator(""), None);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let shortcuts = Shortcuts::default();
+        assert_eq!(shortcuts.resolve("Ctrl+1"), Some(ShortcutAction::ActivateProfile(0)));
+        assert_eq!(shortcuts.resolve("Ctrl+9"), Some(ShortcutAction::ActivateProfile(8)));
+        assert_eq!(shortcuts.resolve("Ctrl+0"), None);
+        assert_eq!(shortcuts.resolve("Ctrl+Tab"), Some(ShortcutAction::NextProfile));
+        assert_eq!(shortcuts.resolve("Ctrl+Shift+Tab"), Some(ShortcutAction::PreviousProfile));
+        assert_eq!(shortcuts.resolve("Ctrl+R"), Some(ShortcutAction::ReloadProfile));
+        assert_eq!(shortcuts.resolve("Ctrl+,"), Some(ShortcutAction::OpenSettings));
+        assert_eq!(shortcuts.resolve("ctrl+k"), Some(ShortcutAction::OpenPalette));
+        assert_eq!(shortcuts.resolve("Ctrl+Shift+1"), None);
+        assert_eq!(shortcuts.bindings().len(), 14);
+
+        let remapped = Shortcuts {
+            profile_number: "alt".to_string(),
+            reload_profile: "F5".to_string(),
+            open_settings: String::new(),
+            ..Shortcuts::default()
+        };
+        assert_eq!(remapped.resolve("Alt+3"), Some(ShortcutAction::ActivateProfile(2)));
+        assert_eq!(remapped.resolve("Ctrl+3"), None);
+        assert_eq!(remapped.resolve("F5"), Some(ShortcutAction::ReloadProfile));
+        assert_eq!(remapped.resolve("Ctrl+,"), None);
+    }
+
+    #[test]
+    fn test_normalized() {
+        let shortcuts = Shortcuts {
+            profile_number: "shift+ctrl".to_string(),
+            reload_profile: " ctrl+shift+r ".to_string(),
+            open_settings: "  ".to_string(),
+            ..Shortcuts::default()
+        }
+        .normalized()
+        .unwrap();
+        assert_eq!(shortcuts.profile_number, "Ctrl+Shift");
+        assert_eq!(shortcuts.reload_profile, "Ctrl+Shift+R");
+        assert_eq!(shortcuts.open_settings, "");
+
+        let invalid = Shortcuts { reload_profile: "R".to_string(), ..Shortcuts::default() };
+        assert!(matches!(invalid.normalized(), Err(Error::Config(_))));
+        let duplicate = Shortcuts { next_profile: "Ctrl+2".to_string(), ..Shortcuts::default() };
+        assert!(matches!(duplicate.normalized(), Err(Error::Config(message)) if message.contains("Ctrl+2")));
+    }
+}
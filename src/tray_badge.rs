@@ -0,0 +1,151 @@
+use image::{Rgba, RgbaImage};
+
+const BADGE_COLOR: Rgba<u8> = Rgba([255, 59, 48, 255]);
+const TEXT_COLOR: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+// Fonte bitmap 3x5 (um u8 por linha, 3 bits menos significativos)
+const GLYPH_WIDTH: u32 = 3;
+const GLYPH_HEIGHT: u32 = 5;
+
+fn glyph(c: char) -> Option<[u8; 5]> {
+    match c {
+        '0' => Some([0b111, 0b101, 0b101, 0b101, 0b111]),
+        '1' => Some([0b010, 0b110, 0b010, 0b010, 0b111]),
+        '2' => Some([0b111, 0b001, 0b111, 0b100, 0b111]),
+        '3' => Some([0b111, 0b001, 0b111, 0b001, 0b111]),
+        '4' => Some([0b101, 0b101, 0b111, 0b001, 0b001]),
+        '5' => Some([0b111, 0b100, 0b111, 0b001, 0b111]),
+        '6' => Some([0b111, 0b100, 0b111, 0b101, 0b111]),
+        '7' => Some([0b111, 0b001, 0b001, 0b001, 0b001]),
+        '8' => Some([0b111, 0b101, 0b111, 0b101, 0b111]),
+        '9' => Some([0b111, 0b101, 0b111, 0b001, 0b111]),
+        '+' => Some([0b000, 0b010, 0b111, 0b010, 0b000]),
+        _ => None,
+    }
+}
+
+/// Texto exibido no badge ("99+" acima de 99)
+pub fn badge_text(count: u32) -> String {
+    if count > 99 {
+        "99+".to_string()
+    } else {
+        count.to_string()
+    }
+}
+
+/// Desenha um badge numérico no canto superior direito do ícone.
+/// Com `count == 0` o ícone é retornado sem alterações.
+pub fn render_badge(base: &RgbaImage, count: u32) -> RgbaImage {
+    let mut image = base.clone();
+    if count == 0 {
+        return image;
+    }
+
+    let (width, height) = image.dimensions();
+    let diameter = (width.min(height) * 3 / 5).max(GLYPH_HEIGHT + 4);
+    let radius = diameter as f32 / 2.0;
+    let center_x = width as f32 - radius;
+    let center_y = radius;
+
+    // Círculo do badge
+    for y in 0..diameter.min(height) {
+        for x in width.saturating_sub(diameter)..width {
+            let dx = x as f32 + 0.5 - center_x;
+            let dy = y as f32 + 0.5 - center_y;
+            if dx * dx + dy * dy <= radius * radius {
+                image.put_pixel(x, y, BADGE_COLOR);
+            }
+        }
+    }
+
+    // Texto centralizado, com a maior escala que cabe no círculo
+    let text = badge_text(count);
+    let chars = text.chars().count() as u32;
+    let text_units = chars * GLYPH_WIDTH + (chars - 1);
+    let scale_by_width = (diameter as f32 * 0.75 / text_units as f32).floor() as u32;
+    let scale_by_height = (diameter as f32 * 0.6 / GLYPH_HEIGHT as f32).floor() as u32;
+    let scale = scale_by_width.min(scale_by_height).max(1);
+
+    let text_width = text_units * scale;
+    let text_height = GLYPH_HEIGHT * scale;
+    let origin_x = (center_x - text_width as f32 / 2.0).round().max(0.0) as u32;
+    let origin_y = (center_y - text_height as f32 / 2.0).round().max(0.0) as u32;
+
+    for (index, c) in text.chars().enumerate() {
+        let Some(rows) = glyph(c) else { continue };
+        let glyph_x = origin_x + index as u32 * (GLYPH_WIDTH + 1) * scale;
+
+        for (row, bits) in rows.iter().enumerate() {
+            for col in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - col)) == 0 {
+                    continue;
+                }
+                for sy in 0..scale {
+                    for sx in 0..scale {
+                        let x = glyph_x + col * scale + sx;
+                        let y = origin_y + row as u32 * scale + sy;
+                        if x < width && y < height {
+                            image.put_pixel(x, y, TEXT_COLOR);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    image
+}
+
+/// Soma das não lidas. As contagens vêm do título das páginas, então um
+/// valor absurdo satura em vez de estourar.
+pub fn total_unread(unread: &[(String, u32)]) -> u32 {
+    unread.iter().fold(0u32, |total, (_, count)| total.saturating_add(*count))
+}
+
+/// Tooltip do tray com o total e a contagem por perfil
+pub fn tooltip_text(unread: &[(String, u32)]) -> String {
+    let total = total_unread(unread);
+    if total == 0 {
+        return "Feather Alloy".to_string();
+    }
+
+    let mut lines = vec![format!("Feather Alloy - {} não lidas", total)];
+    for (name, count) in unread.iter().filter(|(_, count)| *count > 0) {
+        lines.push(format!("{}: {}", name, count));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_icon() -> RgbaImage {
+        RgbaImage::from_pixel(32, 32, Rgba([0, 0, 0, 0]))
+    }
+
+    #[test]
+    fn test_render_badge() {
+        let base = base_icon();
+        assert_eq!(render_badge(&base, 0), base);
+
+        let badged = render_badge(&base, 7);
+        assert_ne!(badged, base);
+        // Canto superior esquerdo fica intacto, canto superior direito recebe o badge
+        assert_eq!(*badged.get_pixel(2, 2), Rgba([0, 0, 0, 0]));
+        assert_ne!(*badged.get_pixel(26, 4), Rgba([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_badge_text_and_tooltip() {
+        assert_eq!(badge_text(5), "5");
+        assert_eq!(badge_text(150), "99+");
+
+        let unread = vec![("Gmail".to_string(), 3), ("Slack".to_string(), 0), ("WhatsApp".to_string(), 2)];
+        assert_eq!(tooltip_text(&unread), "Feather Alloy - 5 não lidas\nGmail: 3\nWhatsApp: 2");
+        assert_eq!(tooltip_text(&[]), "Feather Alloy");
+
+        let hostile = vec![("A".to_string(), u32::MAX), ("B".to_string(), 1)];
+        assert_eq!(total_unread(&hostile), u32::MAX);
+    }
+}
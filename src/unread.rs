@@ -0,0 +1,76 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::Error;
+
+/// Padrões usados quando o perfil não define os seus.
+/// Cobrem títulos como "(3) WhatsApp", "[3] Slack" e "Inbox (3) - fulano@gmail.com - Gmail".
+pub const DEFAULT_UNREAD_PATTERNS: &[&str] = &[
+    r"^\((\d+)\+?\)",
+    r"^\[(\d+)\+?\]",
+    r"^[^(]+\((\d+)\+?\)\s+-",
+];
+
+static DEFAULT_REGEXES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    DEFAULT_UNREAD_PATTERNS
+        .iter()
+        .map(|pattern| Regex::new(pattern).expect("padrão padrão de não lidas inválido"))
+        .collect()
+});
+
+/// Compila os padrões de um perfil. O primeiro padrão inválido recusa a lista inteira.
+pub fn compile_patterns(patterns: &[String]) -> Result<Vec<Regex>, Error> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern).map_err(|_| Error::Config(format!("padrão de não lidas inválido: {}", pattern)))
+        })
+        .collect()
+}
+
+/// Extrai a contagem de mensagens não lidas de um título de página.
+///
+/// Usa o primeiro padrão que casar; o número é lido do primeiro grupo de
+/// captura (ou do match inteiro, se o padrão não tiver grupos). Sem padrões
+/// configurados, usa `DEFAULT_UNREAD_PATTERNS`. Retorna 0 se nada casar.
+pub fn parse_unread_count(title: &str, patterns: &[Regex]) -> u32 {
+    let patterns = if patterns.is_empty() { DEFAULT_REGEXES.as_slice() } else { patterns };
+
+    for regex in patterns {
+        if let Some(captures) = regex.captures(title) {
+            let matched = captures.get(1).or_else(|| captures.get(0));
+            if let Some(count) = matched.and_then(|m| m.as_str().trim().parse::<u32>().ok()) {
+                return count;
+            }
+        }
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_patterns() {
+        assert_eq!(parse_unread_count("(3) WhatsApp", &[]), 3);
+        assert_eq!(parse_unread_count("[12] Slack | Geral", &[]), 12);
+        assert_eq!(parse_unread_count("(99+) Discord", &[]), 99);
+        assert_eq!(parse_unread_count("Inbox (7) - fulano@gmail.com - Gmail", &[]), 7);
+        assert_eq!(parse_unread_count("WhatsApp", &[]), 0);
+        assert_eq!(parse_unread_count("Reunião de 2024 - Calendar", &[]), 0);
+    }
+
+    #[test]
+    fn test_custom_patterns() {
+        let patterns = compile_patterns(&[r"Chat - (\d+) novas".to_string()]).unwrap();
+        assert_eq!(parse_unread_count("Chat - 5 novas", &patterns), 5);
+        // Padrões próprios substituem os padrões padrão
+        assert_eq!(parse_unread_count("(3) Chat", &patterns), 0);
+
+        let invalid = compile_patterns(&["invalid(".to_string(), r"(\d+)".to_string()]);
+        assert!(matches!(invalid, Err(Error::Config(message)) if message.contains("invalid(")));
+    }
+}
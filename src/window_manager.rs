@@ -1,12 +1,14 @@
-use std::path::PathBuf;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 use tao::{
     dpi::{LogicalSize, PhysicalPosition, PhysicalSize},
     event::{Event, WindowEvent},
     event_loop::{ControlFlow, EventLoop, EventLoopProxy},
     window::{Window, WindowBuilder},
 };
-use wry::{Rect, WebView, WebViewBuilder, WebContext};
+use wry::{NewWindowResponse, PageLoadEvent, Rect, WebView, WebViewBuilder, WebContext};
 #[cfg(target_os = "linux")]
 use wry::WebViewBuilderExtUnix;
 #[cfg(target_os = "linux")]
@@ -14,32 +16,47 @@ use tao::platform::unix::WindowExtUnix;
 #[cfg(target_os = "linux")]
 use gtk::prelude::*;
 use tray_icon::TrayIconBuilder;
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
 
-use crate::ipc::{IpcHandler, IpcMessage};
-use crate::profile::AppState;
+use crate::Error;
+use crate::controller::{AppController, CloseAction, DiskStorage, ViewCommand};
+use crate::ipc::{IpcMessage, IpcRequest, IpcResponse, IpcSource};
+use crate::links::{LinkPolicy, LinkTarget};
+use crate::downloads::DownloadManager;
+use crate::paths::Paths;
+use crate::persistence::ImportMode;
+use crate::profile::{AppState, WebProfile};
+use crate::shortcuts::Shortcuts;
 
 const TOOLBAR_WIDTH: f64 = 70.0;
+// Intervalo entre verificações de perfis ociosos para hibernação
+const HIBERNATION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// IDs dos itens do menu do tray; os perfis usam o prefixo seguido do UUID
+const TRAY_TOGGLE_ID: &str = "tray-toggle";
+const TRAY_QUIT_ID: &str = "tray-quit";
+const TRAY_PROFILE_PREFIX: &str = "tray-profile:";
+/// Script de atalhos instalado em todas as webviews
+const SHORTCUTS_SHIM: &str = include_str!("../ui/shortcuts.js");
 
 // Eventos customizados para o event loop
 #[derive(Debug, Clone)]
 pub enum AppEvent {
     ShowAddProfileForm,
     CancelAddProfile,
-    AddProfile {
-        name: String,
-        url: String,
-        icon_path: Option<String>,
-        user_agent: Option<String>,
+    ShowProfile {
+        uuid: String,
     },
-    UpdateProfile {
+    ShowReloadedProfile {
         uuid: String,
-        name: String,
-        url: String,
-        icon_path: Option<String>,
-        user_agent: Option<String>,
     },
-    ShowProfile {
+    ActivateProfile {
+        uuid: String,
+    },
+    ShowNotification {
         uuid: String,
+        title: String,
+        body: String,
     },
     ShowProfileContextMenu {         uuid: String,         x: f64,         y: f64,     },
     ReloadProfile {
@@ -55,23 +72,88 @@ pub enum AppEvent {
         uuid: String,
     },
     SelectIcon,
+    TitleChanged {
+        uuid: String,
+        title: String,
+    },
     FaviconFetched {
         uuid: String,
         path: String,
     },
     IconSelected {
         path: String,
+        preview: Option<String>,
     },
     UpdateToolbar,
     ShowWelcome,
     ShowSettings,
-    UpdateSettings {
-        minimize_on_open: bool,
-        minimize_on_close: bool,
-        hide_on_close: bool,
-        enable_tray: bool,
+    IpcRequest {
+        source: IpcSource,
+        request: IpcRequest,
+    },
+    IpcRejected {
+        source: IpcSource,
+        id: u64,
+        reason: String,
+    },
+    ShowError {
+        message: String,
+    },
+    ContentLoaded,
+    ProfileLoaded {
+        uuid: String,
+    },
+    Shortcut {
+        combo: String,
+    },
+    SelectDownloadDir,
+    DownloadDirSelected {
+        path: String,
     },
+    // Arquivo escolhido para um pedido ImportData (`request` é o id da chamada)
+    ImportArchive {
+        path: PathBuf,
+        mode: ImportMode,
+        request: u64,
+    },
+    ImportFerdiumDir {
+        path: PathBuf,
+        copy_sessions: bool,
+        request: u64,
+    },
+    OpenLogs,
+    // Fim de exportação/importação: mensagem de sucesso, None se o usuário
+    // cancelou o seletor de arquivos, ou o erro
+    BackupFinished {
+        request: u64,
+        result: Result<Option<String>, String>,
+    },
+    ShowDownloads,
+    RefreshDownloads,
+    DownloadStarted {
+        uuid: String,
+        url: String,
+        path: PathBuf,
+    },
+    DownloadFinished {
+        uuid: String,
+        url: String,
+        path: Option<PathBuf>,
+        success: bool,
+    },
+    OpenDownload {
+        id: u64,
+    },
+    ShowDownloadInFolder {
+        id: u64,
+    },
+    OpenDownloadsFolder,
+    ClearDownloads,
+    CheckHibernation,
+    /// Menu da bandeja: alterna entre visível e oculta
     ToggleWindow,
+    /// Atalho global e `--toggle`: traz a janela para frente, ou a oculta se já estiver em foco
+    SummonWindow,
     Quit,
 }
 
@@ -85,30 +167,54 @@ pub struct WindowManager {
     welcome_webview: WebView,
     // WebViews por perfil (UUID -> WebView)
     profile_webviews: HashMap<String, WebView>,
+    paths: Paths,
     state: AppState,
+    // Regras de perfis e configurações; a janela só executa os comandos gerados
+    controller: AppController,
     current_profile_uuid: Option<String>,
     proxy: EventLoopProxy<AppEvent>,
     // WebContexts por perfil
     web_contexts: HashMap<String, WebContext>,
+    // Último momento em que cada perfil deixou de ser exibido
+    last_active: HashMap<String, Instant>,
+    // Perfis cuja webview foi descartada por inatividade (WebContext mantido)
+    hibernated_profiles: HashSet<String>,
+    // Contagem de não lidas extraída do título de cada perfil
+    unread_counts: HashMap<String, u32>,
+    downloads: DownloadManager,
     tray: Option<tray_icon::TrayIcon>,
+    // Ícone do tray já decodificado, base para desenhar o badge de não lidas
+    tray_image: Option<image::RgbaImage>,
+    // Perfil exibido quando a paleta de comandos foi aberta (Esc volta para ele)
+    palette_return: Option<String>,
+    // Criado no primeiro registro do atalho global de mostrar/ocultar
+    hotkey_manager: Option<GlobalHotKeyManager>,
+    global_hotkey: Option<HotKey>,
     context_menu: Option<crate::context_menu::ProfileContextMenu>,
     context_menu_target_uuid: Option<String>,
+    // Página de conteúdo carregando: avisos esperam o fim do carregamento
+    content_loading: Cell<bool>,
+    pending_notices: RefCell<Vec<IpcMessage>>,
 }
 
 impl WindowManager {
     pub fn new(
         event_loop: &EventLoop<AppEvent>,
         state: AppState,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
+        paths: Paths,
+    ) -> Result<Self, Error> {
         // Carregar perfis e configurações salvos
-        let saved_profiles = crate::persistence::load_profiles()?;
-        let saved_settings = crate::persistence::load_settings()?;
+        let saved_profiles = crate::persistence::load_profiles(&paths)?;
+        let saved_settings = crate::persistence::load_settings(&paths)?;
+        let saved_workspaces = crate::persistence::load_workspaces(&paths)?;
         
         {
             let mut data = state.lock().unwrap();
             data.profiles = saved_profiles;
+            data.workspaces = saved_workspaces;
             data.settings = saved_settings;
-            println!("[WindowManager] Loaded {} profiles and settings from disk", data.profiles.len());
+            crate::logging::set_verbose(data.settings.verbose_logging);
+            log::info!("Loaded {} profiles and settings from disk", data.profiles.len());
         }
         
         let icon = Self::load_icon().ok();
@@ -145,12 +251,14 @@ impl WindowManager {
             (hbox, content_box, toolbar_box)
         };
         
+        let shortcuts = state.lock().unwrap().settings.shortcuts.clone();
         let toolbar_webview = Self::create_toolbar_webview(
             &window,
             #[cfg(target_os = "linux")]
             &toolbar_container,
             window_size,
-            state.clone(),
+            paths.data_dir(),
+            &shortcuts,
             proxy.clone(),
         )?;
         
@@ -159,7 +267,8 @@ impl WindowManager {
             #[cfg(target_os = "linux")]
             &content_container,
             window_size,
-            state.clone(),
+            paths.data_dir(),
+            &shortcuts,
             proxy.clone(),
         )?;
 
@@ -172,141 +281,241 @@ impl WindowManager {
             toolbar_webview,
             welcome_webview,
             profile_webviews: HashMap::new(),
+            controller: AppController::new(state.clone(), Box::new(DiskStorage::new(paths.clone()))),
+            paths,
             state,
             current_profile_uuid: None,
             proxy: proxy.clone(),
             web_contexts: HashMap::new(),
+            last_active: HashMap::new(),
+            hibernated_profiles: HashSet::new(),
+            unread_counts: HashMap::new(),
+            downloads: DownloadManager::new(),
             tray: None,
+            tray_image: None,
+            palette_return: None,
+            hotkey_manager: None,
+            global_hotkey: None,
             context_menu: crate::context_menu::ProfileContextMenu::new().ok(),
             context_menu_target_uuid: None,
+            content_loading: Cell::new(true),
+            pending_notices: RefCell::new(Vec::new()),
         };
 
         if manager.state.lock().unwrap().settings.enable_tray {
-            match Self::setup_tray(proxy.clone()) {
-                Ok(tray) => manager.tray = Some(tray),
-                Err(e) => eprintln!("[WindowManager] Failed to setup tray: {}", e),
+            let menu = Self::build_tray_menu(&manager.state.lock().unwrap().profiles);
+            match menu.and_then(|menu| Self::setup_tray(proxy.clone(), menu)) {
+                Ok((tray, image)) => {
+                    manager.tray = Some(tray);
+                    manager.tray_image = Some(image);
+                }
+                Err(e) => log::error!("Failed to setup tray: {}", e),
             }
         }
 
+        let result = manager.register_global_hotkey();
+        manager.report("Não foi possível registrar o atalho global", result);
+
+        Self::spawn_hibernation_timer(proxy.clone());
+
         // Minimizar janela ao abrir se configurado
         if manager.state.lock().unwrap().settings.minimize_on_open {
-            println!("[WindowManager] minimize_on_open is enabled, minimizing window");
+            log::info!("minimize_on_open is enabled, minimizing window");
             manager.window.set_minimized(true);
         }
 
         Ok(manager)
     }
 
-    fn setup_tray(proxy: EventLoopProxy<AppEvent>) -> Result<tray_icon::TrayIcon, Box<dyn std::error::Error>> {
-        use tray_icon::menu::{Menu, MenuItem};
-        
-        println!("[WindowManager] Loading tray icon (32x32.png)...");
-        let icon_bytes = include_bytes!("../icons/32x32.png");
-        let image = image::load_from_memory(icon_bytes)?.to_rgba8();
+    /// Cria o tray e retorna também a imagem do ícone, reaproveitada nos badges
+    fn setup_tray(
+        proxy: EventLoopProxy<AppEvent>,
+        menu: tray_icon::menu::Menu,
+    ) -> Result<(tray_icon::TrayIcon, image::RgbaImage), Error> {
+        log::debug!("Loading tray icon (32x32.png)...");
+        let image = Self::load_tray_image()?;
         let (width, height) = image.dimensions();
-        let rgba = image.into_raw();
-        let tray_icon = tray_icon::Icon::from_rgba(rgba, width, height)?;
-
-        // Criar menu para o tray (necessário no Linux onde eventos de clique não funcionam)
-        let menu = Menu::new();
-        let toggle_item = MenuItem::new("Show/Hide", true, None);
-        let quit_item = MenuItem::new("Quit", true, None);
-        
-        menu.append(&toggle_item)?;
-        menu.append(&quit_item)?;
-
-        // Armazenar os IDs antes de criar a closure
-        let toggle_id = toggle_item.id().clone();
-        let quit_id = quit_item.id().clone();
+        let rgba = image.clone().into_raw();
+        let tray_icon = tray_icon::Icon::from_rgba(rgba, width, height).map_err(|e| Error::WebView(e.to_string()))?;
 
-        // Configurar handler para eventos de menu
+        // Configurar handler para eventos de menu. Os IDs são fixos para que o
+        // handler continue valendo quando o menu é recriado.
         let proxy_clone = proxy.clone();
         tray_icon::menu::MenuEvent::set_event_handler(Some(move |event: tray_icon::menu::MenuEvent| {
-            println!("[WindowManager] Menu event: {:?}", event);
-            if event.id == toggle_id {
+            log::debug!("Menu event: {:?}", event);
+            let id = event.id.0.as_str();
+            if id == TRAY_TOGGLE_ID {
                 let _ = proxy_clone.send_event(AppEvent::ToggleWindow);
-            } else if event.id == quit_id {
+            } else if id == TRAY_QUIT_ID {
                 let _ = proxy_clone.send_event(AppEvent::Quit);
+            } else if let Some(uuid) = id.strip_prefix(TRAY_PROFILE_PREFIX) {
+                let _ = proxy_clone.send_event(AppEvent::ActivateProfile { uuid: uuid.to_string() });
             }
         }));
 
-        println!("[WindowManager] Building tray icon with menu");
+        log::debug!("Building tray icon with menu");
         let tray = TrayIconBuilder::new()
             .with_icon(tray_icon)
             .with_tooltip("Feather Alloy")
             .with_title("Feather Alloy")
             .with_id("feather-alloy-tray")
             .with_menu(Box::new(menu))
-            .build()?;
+            .build()
+            .map_err(|e| Error::WebView(e.to_string()))?;
             
-        println!("[WindowManager] Tray icon built successfully");
-        Ok(tray)
+        log::debug!("Tray icon built successfully");
+        Ok((tray, image))
     }
 
-    fn load_icon() -> Result<tao::window::Icon, Box<dyn std::error::Error>> {
-        let icon_bytes = include_bytes!("../icons/128x128.png");
-        let image = image::load_from_memory(icon_bytes)?.to_rgba8();
-        let (width, height) = image.dimensions();
-        let rgba = image.into_raw();
-        tao::window::Icon::from_rgba(rgba, width, height).map_err(|e| e.into())
+    /// Menu do tray (necessário no Linux onde eventos de clique não funcionam):
+    /// os perfis na ordem da barra lateral, seguidos de Show/Hide e Quit
+    fn build_tray_menu(profiles: &[WebProfile]) -> Result<tray_icon::menu::Menu, Error> {
+        use tray_icon::menu::{Menu, MenuItem, PredefinedMenuItem};
+
+        let menu = Menu::new();
+        for profile in profiles {
+            let item = MenuItem::with_id(format!("{}{}", TRAY_PROFILE_PREFIX, profile.uuid), &profile.name, true, None);
+            menu.append(&item).map_err(|e| Error::WebView(e.to_string()))?;
+        }
+        if !profiles.is_empty() {
+            menu.append(&PredefinedMenuItem::separator()).map_err(|e| Error::WebView(e.to_string()))?;
+        }
+        menu.append(&MenuItem::with_id(TRAY_TOGGLE_ID, "Show/Hide", true, None))
+            .map_err(|e| Error::WebView(e.to_string()))?;
+        menu.append(&MenuItem::with_id(TRAY_QUIT_ID, "Quit", true, None))
+            .map_err(|e| Error::WebView(e.to_string()))?;
+        Ok(menu)
     }
 
-    fn asset_protocol_handler(_id: wry::WebViewId, request: http::Request<Vec<u8>>) -> http::Response<std::borrow::Cow<'static, [u8]>> {
-        let uri = request.uri();
-        
-        // Em WRY, o esquema asset://caminho/para/arquivo pode vir com o host sendo a primeira parte do caminho
-        let path_str = if let Some(host) = uri.host() {
-            format!("{}{}", host, uri.path())
-        } else {
-            uri.path().to_string()
+    /// Recria o menu do tray depois que perfis são adicionados, renomeados ou reordenados
+    fn update_tray_menu(&self) -> Result<(), Error> {
+        let Some(tray) = &self.tray else {
+            return Ok(());
         };
-        
-        // Remover barra inicial se existir para facilitar o join com o path relativo
-        // if path_str.starts_with('/') {
-        //    path_str.remove(0);
-        // }
-
-        let path = if path_str.starts_with("profiles/") {
-             // Resolve against data dir
-             if let Some(data_dir) = dirs::data_dir() {
-                 data_dir.join("feather-alloy").join(&path_str)
-             } else {
-                 std::path::PathBuf::from(&path_str)
-             }
+        let menu = Self::build_tray_menu(&self.state.lock().unwrap().profiles)?;
+        tray.set_menu(Some(Box::new(menu)));
+        Ok(())
+    }
+
+    /// Registra o atalho global das configurações no lugar do anterior.
+    ///
+    /// O registro usa o X11: no Wayland o compositor só repassa as teclas quando
+    /// uma janela XWayland está em foco. Lá, a alternativa é associar
+    /// `feather-alloy --toggle` a um atalho do ambiente de trabalho.
+    fn register_global_hotkey(&mut self) -> Result<(), Error> {
+        if let (Some(manager), Some(hotkey)) = (&self.hotkey_manager, self.global_hotkey.take()) {
+            if let Err(e) = manager.unregister(hotkey) {
+                log::warn!("Failed to unregister global hotkey: {}", e);
+            }
+        }
+
+        let combo = self.state.lock().unwrap().settings.global_hotkey.clone();
+        let Some(accelerator) = crate::shortcuts::global_accelerator(&combo) else {
+            return Ok(());
+        };
+        let hotkey: HotKey = accelerator
+            .parse()
+            .map_err(|e| Error::Config(format!("atalho global {}: {}", combo, e)))?;
+
+        if self.hotkey_manager.is_none() {
+            self.hotkey_manager = Some(Self::setup_global_hotkey(self.proxy.clone())?);
+        }
+        if let Some(manager) = &self.hotkey_manager {
+            manager.register(hotkey).map_err(|e| Error::WebView(e.to_string()))?;
+        }
+        self.global_hotkey = Some(hotkey);
+
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            log::info!("Global hotkey {} registered through XWayland; bind `feather-alloy --toggle` in the desktop settings if it does not fire", combo);
         } else {
-             std::path::PathBuf::from(&path_str)
+            log::info!("Global hotkey {} registered", combo);
+        }
+        Ok(())
+    }
+
+    fn setup_global_hotkey(proxy: EventLoopProxy<AppEvent>) -> Result<GlobalHotKeyManager, Error> {
+        let manager = GlobalHotKeyManager::new().map_err(|e| Error::WebView(e.to_string()))?;
+        GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
+            log::debug!("Global hotkey event: {:?}", event);
+            if event.state == HotKeyState::Pressed {
+                let _ = proxy.send_event(AppEvent::SummonWindow);
+            }
+        }));
+        Ok(manager)
+    }
+
+    fn spawn_hibernation_timer(proxy: EventLoopProxy<AppEvent>) {
+        std::thread::spawn(move || loop {
+            std::thread::sleep(HIBERNATION_CHECK_INTERVAL);
+            // O envio só falha quando o event loop já foi encerrado
+            if proxy.send_event(AppEvent::CheckHibernation).is_err() {
+                break;
+            }
+        });
+    }
+
+    fn load_tray_image() -> Result<image::RgbaImage, Error> {
+        let icon_bytes = include_bytes!("../icons/32x32.png");
+        let image = image::load_from_memory(icon_bytes).map_err(|e| Error::WebView(e.to_string()))?;
+        Ok(image.to_rgba8())
+    }
+
+    /// Atualiza o ícone do tray com o total de não lidas e o tooltip com a contagem por perfil
+    pub fn update_tray_unread(&self) -> Result<(), Error> {
+        let (Some(tray), Some(base)) = (&self.tray, &self.tray_image) else {
+            return Ok(());
         };
         
-        // println!("[AssetProtocol] Requesting: {:?} (from URI: {})", path, uri);
-        
-        match std::fs::read(&path) {
-            Ok(content) => {
-                let mime_type = if path.extension().map(|e| e == "png").unwrap_or(false) {
-                    "image/png"
-                } else if path.extension().map(|e| e == "svg").unwrap_or(false) {
-                    "image/svg+xml"
-                } else if path.extension().map(|e| e == "ico").unwrap_or(false) {
-                    "image/x-icon"
-                } else if path.extension().map(|e| e == "html").unwrap_or(false) {
-                    "text/html"
-                } else if path.extension().map(|e| e == "css").unwrap_or(false) {
-                    "text/css"
-                } else if path.extension().map(|e| e == "js").unwrap_or(false) {
-                    "text/javascript"
-                } else {
-                    "application/octet-stream"
-                };
-                
-                http::Response::builder()
-                    .header("Content-Type", mime_type)
-                    .header("Access-Control-Allow-Origin", "*")
-                    .body(std::borrow::Cow::from(content))
-                    .unwrap()
+        let data = self.state.lock().unwrap();
+        let unread: Vec<(String, u32)> = data.profiles.iter()
+            .filter_map(|p| self.unread_counts.get(&p.uuid).map(|count| (p.name.clone(), *count)))
+            .collect();
+        drop(data);
+        
+        let image = crate::tray_badge::render_badge(base, crate::tray_badge::total_unread(&unread));
+        let (width, height) = image.dimensions();
+        let icon = tray_icon::Icon::from_rgba(image.into_raw(), width, height).map_err(|e| Error::WebView(e.to_string()))?;
+        
+        tray.set_icon(Some(icon)).map_err(|e| Error::WebView(e.to_string()))?;
+        tray.set_tooltip(Some(crate::tray_badge::tooltip_text(&unread)))
+            .map_err(|e| Error::WebView(e.to_string()))?;
+        Ok(())
+    }
+
+    fn load_icon() -> Result<tao::window::Icon, Error> {
+        let icon_bytes = include_bytes!("../icons/128x128.png");
+        let image = image::load_from_memory(icon_bytes)
+            .map_err(|e| Error::WebView(e.to_string()))?
+            .to_rgba8();
+        let (width, height) = image.dimensions();
+        let rgba = image.into_raw();
+        tao::window::Icon::from_rgba(rgba, width, height).map_err(|e| Error::WebView(e.to_string()))
+    }
+
+    /// Serve `asset://` apenas a partir dos arquivos embutidos e das imagens
+    /// do diretório de dados (ver `crate::assets::resolve`)
+    fn asset_protocol_handler(
+        data_dir: &Path,
+    ) -> impl Fn(wry::WebViewId, http::Request<Vec<u8>>) -> http::Response<std::borrow::Cow<'static, [u8]>> + 'static {
+        let data_dir = data_dir.to_path_buf();
+        move |_id, request| Self::serve_asset(&data_dir, request)
+    }
+
+    fn serve_asset(data_dir: &Path, request: http::Request<Vec<u8>>) -> http::Response<std::borrow::Cow<'static, [u8]>> {
+        let path_str = crate::assets::request_path(request.uri());
+        
+        match crate::assets::resolve(data_dir, &path_str) {
+            Ok(asset) => {
+                let mut response = http::Response::builder().header("Content-Type", asset.mime_type);
+                if asset.untrusted {
+                    response = response.header("Content-Security-Policy", crate::assets::DATA_DIR_CSP);
+                }
+                response.body(asset.content).unwrap()
             }
             Err(e) => {
-                eprintln!("[AssetProtocol] Failed to read asset {:?}: {}", path, e);
+                log::debug!("Refused asset {:?}: {:?}", path_str, e);
                 http::Response::builder()
-                    .status(404)
+                    .status(e.status())
                     .body(std::borrow::Cow::from(Vec::new()))
                     .unwrap()
             }
@@ -319,16 +528,15 @@ impl WindowManager {
         #[cfg(target_os = "linux")]
         container: &gtk::Box,
         window_size: PhysicalSize<u32>,
-        state: AppState,
+        data_dir: &Path,
+        shortcuts: &Shortcuts,
         proxy: EventLoopProxy<AppEvent>,
-    ) -> Result<WebView, Box<dyn std::error::Error>> {
+    ) -> Result<WebView, Error> {
         let toolbar_bounds = Rect {
             position: PhysicalPosition::new(0, 0).into(),
             size: PhysicalSize::new(TOOLBAR_WIDTH as u32, window_size.height).into(),
         };
 
-        let toolbar_html = include_str!("../ui/toolbar/index.html");
-        
         let disable_context_menu = r#"
             document.addEventListener('contextmenu', function(e) {
                 e.preventDefault();
@@ -343,17 +551,37 @@ impl WindowManager {
         
         let builder = WebViewBuilder::new()
             .with_bounds(toolbar_bounds)
-            .with_custom_protocol("asset".into(), Self::asset_protocol_handler)
+            .with_custom_protocol("asset".into(), Self::asset_protocol_handler(data_dir))
             // Scripts de inicialização executados em ordem
             .with_initialization_script(disable_context_menu)
             .with_initialization_script(init_script)
+            .with_initialization_script(Self::shortcut_bindings_script(shortcuts))
+            .with_initialization_script(SHORTCUTS_SHIM)
             .with_devtools(false) // Desabilitar DevTools
-            .with_html(toolbar_html)
+            .with_url(&crate::assets::url("ui/toolbar/index.html"))
+            .with_navigation_handler(Self::ui_navigation_handler)
             .with_ipc_handler(move |request: http::Request<String>| {
                 let body = request.body();
+                let origin = request.uri();
+                
+                // Requisições com resposta são atendidas no event loop
+                if let Ok(request) = IpcRequest::from_json(body) {
+                    if let Err(reason) = IpcSource::Toolbar.authorize(origin, &request.message) {
+                        log::warn!("Rejected toolbar request #{} {:?}: {}", request.id, request.message, reason);
+                        let _ = proxy.send_event(AppEvent::IpcRejected { source: IpcSource::Toolbar, id: request.id, reason });
+                        return;
+                    }
+                    log::debug!("Toolbar request #{}: {:?}", request.id, request.message);
+                    let _ = proxy.send_event(AppEvent::IpcRequest { source: IpcSource::Toolbar, request });
+                    return;
+                }
                 
                 if let Ok(message) = IpcMessage::from_json(body) {
-                    println!("[Toolbar IPC] Received: {:?}", message);
+                    if let Err(reason) = IpcSource::Toolbar.authorize(origin, &message) {
+                        log::warn!("Rejected toolbar message {:?}: {}", message, reason);
+                        return;
+                    }
+                    log::debug!("Toolbar message: {:?}", message);
                     
                     match message {
                         IpcMessage::ShowAddProfileForm => {
@@ -371,6 +599,9 @@ impl WindowManager {
                         IpcMessage::ShowSettings => {
                             let _ = proxy.send_event(AppEvent::ShowSettings);
                         }
+                        IpcMessage::ShowDownloads => {
+                            let _ = proxy.send_event(AppEvent::ShowDownloads);
+                        }
                         IpcMessage::ShowProfileContextMenu { uuid, x, y } => {
                             let _ = proxy.send_event(AppEvent::ShowProfileContextMenu { uuid, x, y });
                         }
@@ -386,12 +617,12 @@ impl WindowManager {
                         IpcMessage::RemoveProfile { uuid } => {
                             let _ = proxy.send_event(AppEvent::RemoveProfile { uuid });
                         }
+                        IpcMessage::Shortcut { combo } => {
+                            let _ = proxy.send_event(AppEvent::Shortcut { combo });
+                        }
 
-                        _ => {
-                            let handler = IpcHandler::new(state.clone());
-                            if let Some(response) = handler.handle_message(message) {
-                                println!("[Toolbar] Response: {:?}", response);
-                            }
+                        other => {
+                            log::warn!("Unhandled toolbar message: {:?}", other);
                         }
                     }
                 }
@@ -418,9 +649,10 @@ impl WindowManager {
         #[cfg(target_os = "linux")]
         container: &gtk::Box,
         window_size: PhysicalSize<u32>,
-        state: AppState,
+        data_dir: &Path,
+        shortcuts: &Shortcuts,
         proxy: EventLoopProxy<AppEvent>,
-    ) -> Result<WebView, Box<dyn std::error::Error>> {
+    ) -> Result<WebView, Error> {
         let content_bounds = Rect {
             position: PhysicalPosition::new(TOOLBAR_WIDTH as i32, 0).into(),
             size: PhysicalSize::new(
@@ -429,8 +661,6 @@ impl WindowManager {
             ).into(),
         };
 
-        let welcome_html = include_str!("../ui/content/index.html");
-
         let disable_context_menu = r#"
             document.addEventListener('contextmenu', function(e) {
                 e.preventDefault();
@@ -444,63 +674,106 @@ impl WindowManager {
 
         let builder = WebViewBuilder::new()
             .with_bounds(content_bounds)
-            .with_custom_protocol("asset".into(), Self::asset_protocol_handler)
+            .with_custom_protocol("asset".into(), Self::asset_protocol_handler(data_dir))
             // Scripts executados em ordem
             .with_initialization_script(disable_context_menu)
             .with_initialization_script(init_script)
+            .with_initialization_script(Self::shortcut_bindings_script(shortcuts))
+            .with_initialization_script(SHORTCUTS_SHIM)
             .with_devtools(false) // Desabilitar DevTools
-            .with_html(welcome_html)
+            .with_url(&crate::assets::url("ui/content/index.html"))
+            .with_navigation_handler(Self::ui_navigation_handler)
+            .with_on_page_load_handler({
+                let proxy = proxy.clone();
+                move |event, _url| {
+                    if let PageLoadEvent::Finished = event {
+                        let _ = proxy.send_event(AppEvent::ContentLoaded);
+                    }
+                }
+            })
             .with_ipc_handler(move |request: http::Request<String>| {
                 let body = request.body();
+                let origin = request.uri();
+                
+                // Requisições com resposta são atendidas no event loop
+                if let Ok(request) = IpcRequest::from_json(body) {
+                    if let Err(reason) = IpcSource::Content.authorize(origin, &request.message) {
+                        log::warn!("Rejected content request #{} {:?}: {}", request.id, request.message, reason);
+                        let _ = proxy.send_event(AppEvent::IpcRejected { source: IpcSource::Content, id: request.id, reason });
+                        return;
+                    }
+                    log::debug!("Content request #{}: {:?}", request.id, request.message);
+                    let _ = proxy.send_event(AppEvent::IpcRequest { source: IpcSource::Content, request });
+                    return;
+                }
                 
                 if let Ok(message) = IpcMessage::from_json(body) {
-                    println!("[Welcome IPC] Received: {:?}", message);
+                    if let Err(reason) = IpcSource::Content.authorize(origin, &message) {
+                        log::warn!("Rejected content message {:?}: {}", message, reason);
+                        return;
+                    }
+                    log::debug!("Content message: {:?}", message);
                     
                     match message {
-                        IpcMessage::AddProfile { name, url, icon_path, user_agent } => {
-                            let _ = proxy.send_event(AppEvent::AddProfile {
-                                name,
-                                url,
-                                icon_path,
-                                user_agent,
-                            });
-                        }
-                        IpcMessage::UpdateProfile { uuid, name, url, icon_path, user_agent } => {
-                            let _ = proxy.send_event(AppEvent::UpdateProfile {
-                                uuid,
-                                name,
-                                url,
-                                icon_path,
-                                user_agent,
-                            });
-                        }
                         IpcMessage::SelectIcon => {
                             let _ = proxy.send_event(AppEvent::SelectIcon);
                         }
-                        IpcMessage::UpdateSettings { minimize_on_open, minimize_on_close, hide_on_close, enable_tray } => {
-                            let _ = proxy.send_event(AppEvent::UpdateSettings {
-                                minimize_on_open,
-                                minimize_on_close,
-                                hide_on_close,
-                                enable_tray,
-                            });
+                        IpcMessage::SelectDownloadDir => {
+                            let _ = proxy.send_event(AppEvent::SelectDownloadDir);
+                        }
+                        IpcMessage::OpenLogs => {
+                            let _ = proxy.send_event(AppEvent::OpenLogs);
+                        }
+                        IpcMessage::GetDownloads => {
+                            let _ = proxy.send_event(AppEvent::RefreshDownloads);
+                        }
+                        IpcMessage::OpenDownload { id } => {
+                            let _ = proxy.send_event(AppEvent::OpenDownload { id });
+                        }
+                        IpcMessage::ShowDownloadInFolder { id } => {
+                            let _ = proxy.send_event(AppEvent::ShowDownloadInFolder { id });
+                        }
+                        IpcMessage::OpenDownloadsFolder => {
+                            let _ = proxy.send_event(AppEvent::OpenDownloadsFolder);
+                        }
+                        IpcMessage::ClearDownloads => {
+                            let _ = proxy.send_event(AppEvent::ClearDownloads);
                         }
                         IpcMessage::CancelAddProfile => {
                             let _ = proxy.send_event(AppEvent::CancelAddProfile);
                         }
                         IpcMessage::ShowWelcome => {
-                            println!("[Welcome IPC] ShowWelcome received, sending ShowWelcome event");
+                            log::debug!("ShowWelcome received from content page");
                             let _ = proxy.send_event(AppEvent::ShowWelcome);
                         }
                         IpcMessage::QuitApp => {
-                            println!("[Welcome IPC] QuitApp received, sending Quit event");
+                            log::debug!("QuitApp received from content page");
                             let _ = proxy.send_event(AppEvent::Quit);
                         }
-                        _ => {
-                            let handler = IpcHandler::new(state.clone());
-                            if let Some(response) = handler.handle_message(message) {
-                                println!("[Welcome] Response: {:?}", response);
-                            }
+                        IpcMessage::Shortcut { combo } => {
+                            let _ = proxy.send_event(AppEvent::Shortcut { combo });
+                        }
+                        // Ações da paleta de comandos
+                        IpcMessage::ShowProfile { uuid } => {
+                            let _ = proxy.send_event(AppEvent::ShowProfile { uuid });
+                        }
+                        IpcMessage::ShowReloadedProfile { uuid } => {
+                            let _ = proxy.send_event(AppEvent::ShowReloadedProfile { uuid });
+                        }
+                        IpcMessage::EditProfile { uuid } => {
+                            let _ = proxy.send_event(AppEvent::ShowEditProfile { uuid });
+                        }
+                        IpcMessage::ShowAddProfileForm => {
+                            let _ = proxy.send_event(AppEvent::ShowAddProfileForm);
+                        }
+                        IpcMessage::ShowSettings => {
+                            let _ = proxy.send_event(AppEvent::ShowSettings);
+                        }
+                        IpcMessage::ShowDownloads => {
+                            let _ = proxy.send_event(AppEvent::ShowDownloads);
+                        }
+                        other => {
+                            log::warn!("Unhandled content message: {:?}", other);
                         }
                     }
                 }
@@ -528,8 +801,13 @@ impl WindowManager {
         container: &gtk::Box,
         window_size: PhysicalSize<u32>,
         web_context: &mut WebContext,
-        url: &str,
-    ) -> Result<WebView, Box<dyn std::error::Error>> {
+        profile: &WebProfile,
+        state: AppState,
+        proxy: EventLoopProxy<AppEvent>,
+    ) -> Result<WebView, Error> {
+        let uuid = profile.uuid.as_str();
+        let link_policy = LinkPolicy::new(&profile.url, &profile.allowed_domains);
+
         let content_bounds = Rect {
             position: PhysicalPosition::new(TOOLBAR_WIDTH as i32, 0).into(),
             size: PhysicalSize::new(
@@ -548,15 +826,160 @@ impl WindowManager {
         let init_script = r#"
             console.log('[Profile] Initialization script running');
         "#;
+        
+        let notifications_shim = include_str!("../ui/profile/notifications.js");
+        let shortcut_bindings = Self::shortcut_bindings_script(&state.lock().unwrap().settings.shortcuts);
 
-        let builder = WebViewBuilder::new_with_web_context(web_context)
+        let mut builder = WebViewBuilder::new_with_web_context(web_context)
             .with_bounds(content_bounds)
             // Scripts executados em ordem
             .with_initialization_script(disable_context_menu)
             .with_initialization_script(init_script)
+            .with_initialization_script(notifications_shim)
+            .with_initialization_script(shortcut_bindings)
+            .with_initialization_script(SHORTCUTS_SHIM)
             .with_devtools(false) // Desabilitar DevTools
-            .with_url(url)
-            .with_visible(false); // Iniciar oculto
+            .with_url(&profile.url)
+            .with_visible(false) // Iniciar oculto
+            // Os scripts de inicialização guardam os atalhos da criação da webview;
+            // a cada carregamento os atuais são reenviados
+            .with_on_page_load_handler({
+                let uuid = uuid.to_string();
+                let proxy = proxy.clone();
+                move |event, _url| {
+                    if let PageLoadEvent::Finished = event {
+                        let _ = proxy.send_event(AppEvent::ProfileLoaded { uuid: uuid.clone() });
+                    }
+                }
+            })
+            .with_document_title_changed_handler({
+                let uuid = uuid.to_string();
+                let proxy = proxy.clone();
+                move |title: String| {
+                    let _ = proxy.send_event(AppEvent::TitleChanged {
+                        uuid: uuid.clone(),
+                        title,
+                    });
+                }
+            })
+            // Conteúdo remoto: apenas notificações são aceitas via IPC
+            .with_ipc_handler({
+                let uuid = uuid.to_string();
+                let proxy = proxy.clone();
+                move |request: http::Request<String>| {
+                    let message = match IpcMessage::from_json(request.body()) {
+                        Ok(message) => message,
+                        Err(e) => {
+                            log::warn!("Invalid message from profile {}: {}", uuid, e);
+                            return;
+                        }
+                    };
+                    if let Err(reason) = IpcSource::Profile.authorize(request.uri(), &message) {
+                        log::warn!("Rejected {:?} from profile {} ({}): {}", message, uuid, request.uri(), reason);
+                        return;
+                    }
+                    
+                    match message {
+                        IpcMessage::ShowNotification { title, body } => {
+                            let _ = proxy.send_event(AppEvent::ShowNotification {
+                                uuid: uuid.clone(),
+                                title,
+                                body,
+                            });
+                        }
+                        IpcMessage::Shortcut { combo } => {
+                            let _ = proxy.send_event(AppEvent::Shortcut { combo });
+                        }
+                        _ => {}
+                    }
+                }
+            });
+
+        // User-Agent customizado do perfil (senão usa o padrão do WebKitGTK)
+        if let Some(user_agent) = &profile.user_agent {
+            builder = builder.with_user_agent(user_agent);
+        }
+        
+        // Downloads vão para a pasta configurada (lida a cada download, para
+        // refletir mudanças nas configurações sem recriar a webview)
+        builder = builder
+            .with_download_started_handler({
+                let uuid = uuid.to_string();
+                let proxy = proxy.clone();
+                move |url: String, destination: &mut PathBuf| {
+                    let data = state.lock().unwrap();
+                    let dir = crate::downloads::download_directory(&data.settings);
+                    let profile_folder = if data.settings.downloads_per_profile {
+                        data.profiles.iter().find(|p| p.uuid == uuid).map(|p| p.name.clone())
+                    } else {
+                        None
+                    };
+                    drop(data);
+                    
+                    let file_name = destination
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    let path = crate::downloads::resolve_destination(&dir, profile_folder.as_deref(), &file_name);
+                    if let Some(parent) = path.parent() {
+                        if let Err(e) = std::fs::create_dir_all(parent) {
+                            log::warn!("Failed to create download directory {:?}: {}", parent, e);
+                            return false;
+                        }
+                    }
+                    
+                    *destination = path.clone();
+                    let _ = proxy.send_event(AppEvent::DownloadStarted {
+                        uuid: uuid.clone(),
+                        url,
+                        path,
+                    });
+                    true
+                }
+            })
+            .with_download_completed_handler({
+                let uuid = uuid.to_string();
+                let proxy = proxy.clone();
+                move |url: String, path: Option<PathBuf>, success: bool| {
+                    let _ = proxy.send_event(AppEvent::DownloadFinished {
+                        uuid: uuid.clone(),
+                        url,
+                        path,
+                        success,
+                    });
+                }
+            });
+        
+        // Popups (target=_blank, window.open): login abre como janela filha que
+        // compartilha o WebContext do perfil; links externos vão para o navegador
+        builder = builder.with_new_window_req_handler({
+            let link_policy = link_policy.clone();
+            move |url: String, _features| match link_policy.classify(&url) {
+                LinkTarget::Internal | LinkTarget::Auth => NewWindowResponse::Allow,
+                LinkTarget::External => {
+                    if let Err(e) = crate::links::open_external(&url) {
+                        log::warn!("Failed to open external link {}: {}", url, e);
+                    }
+                    NewWindowResponse::Deny
+                }
+            }
+        });
+        
+        // No WebView2 o handler de navegação só recebe o frame principal; no
+        // WebKitGTK ele também recebe iframes, então lá usamos decide-policy
+        #[cfg(not(target_os = "linux"))]
+        {
+            let link_policy = link_policy.clone();
+            builder = builder.with_navigation_handler(move |url: String| {
+                if link_policy.classify(&url) != LinkTarget::External {
+                    return true;
+                }
+                if let Err(e) = crate::links::open_external(&url) {
+                    log::warn!("Failed to open external link {}: {}", url, e);
+                }
+                false
+            });
+        }
 
         #[cfg(target_os = "linux")]
         let webview = {
@@ -569,14 +992,66 @@ impl WindowManager {
         };
         #[cfg(not(target_os = "linux"))]
         let webview = builder.build_as_child(window)?;
+        
+        #[cfg(target_os = "linux")]
+        Self::install_link_policy(&webview, link_policy);
 
         Ok(webview)
     }
 
-    fn get_or_create_web_context(&mut self, uuid: &str) -> Result<&mut WebContext, Box<dyn std::error::Error>> {
+    /// Abre no navegador do sistema as navegações do frame principal para fora
+    /// do serviço. O pedido de navegação também chega para iframes, sem como
+    /// distingui-los; por isso ali só são desviados cliques em links e esquemas
+    /// que a webview não carrega (mailto:, tel:). As demais navegações (scripts,
+    /// formulários, redirecionamentos) são desviadas na resposta, que informa se
+    /// o recurso é do frame principal. Assim iframes de terceiros (embeds,
+    /// captchas) continuam carregando dentro da página.
+    #[cfg(target_os = "linux")]
+    fn install_link_policy(webview: &WebView, link_policy: LinkPolicy) {
+        use webkit2gtk::{
+            NavigationPolicyDecision, NavigationPolicyDecisionExt, NavigationType,
+            PolicyDecisionExt, PolicyDecisionType, ResponsePolicyDecision,
+            ResponsePolicyDecisionExt, URIRequestExt, URIResponseExt, WebViewExt,
+        };
+        use wry::WebViewExtUnix;
+        
+        webview.webview().connect_decide_policy(move |_, decision, decision_type| {
+            let url = match decision_type {
+                PolicyDecisionType::NavigationAction => decision
+                    .downcast_ref::<NavigationPolicyDecision>()
+                    .and_then(|d| d.navigation_action())
+                    .and_then(|action| {
+                        let url = action.request().and_then(|r| r.uri())?;
+                        let web = url.starts_with("http:") || url.starts_with("https:");
+                        (action.navigation_type() == NavigationType::LinkClicked || !web).then_some(url)
+                    }),
+                PolicyDecisionType::Response => decision
+                    .downcast_ref::<ResponsePolicyDecision>()
+                    .filter(|d| d.is_main_frame_main_resource())
+                    .and_then(|d| d.response())
+                    .and_then(|r| r.uri()),
+                _ => None,
+            };
+            let Some(url) = url else {
+                return false;
+            };
+            
+            if link_policy.classify(&url) != LinkTarget::External {
+                return false;
+            }
+            if let Err(e) = crate::links::open_external(&url) {
+                log::warn!("Failed to open external link {}: {}", url, e);
+            }
+            decision.ignore();
+            true
+        });
+    }
+
+    fn get_or_create_web_context(&mut self, uuid: &str) -> Result<&mut WebContext, Error> {
         if !self.web_contexts.contains_key(uuid) {
-            let data_dir = Self::get_profile_data_directory(uuid)?;
-            println!("[WindowManager] Creating WebContext for profile {} with data directory: {:?}", uuid, data_dir);
+            let data_dir = self.paths.profile_dir(uuid);
+            std::fs::create_dir_all(&data_dir)?;
+            log::debug!("Creating WebContext for profile {} with data directory: {:?}", uuid, data_dir);
             
             let web_context = WebContext::new(Some(data_dir));
             self.web_contexts.insert(uuid.to_string(), web_context);
@@ -585,56 +1060,52 @@ impl WindowManager {
         Ok(self.web_contexts.get_mut(uuid).unwrap())
     }
 
-    fn get_profile_data_directory(uuid: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
-        let app_data_dir = dirs::data_dir()
-            .ok_or("Failed to get data directory")?
-            .join("feather-alloy")
-            .join("profiles")
-            .join(uuid);
-
-        std::fs::create_dir_all(&app_data_dir)?;
-        Ok(app_data_dir)
-    }
-
-    pub fn show_add_profile_form(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn show_add_profile_form(&mut self) -> Result<(), Error> {
         // Ocultar todas as webviews de perfis
         for webview in self.profile_webviews.values() {
             webview.set_visible(false)?;
         }
         
-        let add_profile_html = include_str!("../ui/content/add-profile.html");
-        self.welcome_webview.load_html(add_profile_html)?;
+        self.load_content(&crate::assets::url("ui/content/add-profile.html"))?;
         self.welcome_webview.set_visible(true)?;
         
-        self.current_profile_uuid = None;
-        println!("[WindowManager] Showing add profile form");
+        self.leave_current_profile();
+        log::debug!("Showing add profile form");
         Ok(())
     }
 
-    pub fn show_welcome(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn show_welcome(&mut self) -> Result<(), Error> {
         // Ocultar todas as webviews de perfis
         for webview in self.profile_webviews.values() {
             webview.set_visible(false)?;
         }
         
-        let welcome_html = include_str!("../ui/content/index.html");
-        self.welcome_webview.load_html(welcome_html)?;
+        self.load_content(&crate::assets::url("ui/content/index.html"))?;
         self.welcome_webview.set_visible(true)?;
         
-        self.current_profile_uuid = None;
-        println!("[WindowManager] Showing welcome screen");
+        self.leave_current_profile();
+        log::debug!("Showing welcome screen");
         Ok(())
     }
 
-    pub fn navigate_to_profile(&mut self, uuid: &str) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn navigate_to_profile(&mut self, uuid: &str) -> Result<(), Error> {
         let data = self.state.lock().unwrap();
         
         if let Some(profile) = data.profiles.iter().find(|p| p.uuid == uuid) {
-            let url = profile.url.clone();
-            let name = profile.name.clone();
+            let profile = profile.clone();
             drop(data);
             
-            println!("[WindowManager] Navigating to profile: {} ({})", name, url);
+            // Perfis desativados não carregam a webview; abrir a edição para reativar
+            if !profile.enabled {
+                log::info!("Profile {} is disabled, showing edit form", profile.name);
+                return self.show_edit_profile(uuid);
+            }
+            
+            log::debug!("Navigating to profile: {} ({})", profile.name, profile.url);
+            
+            if self.current_profile_uuid.as_deref() != Some(uuid) {
+                self.leave_current_profile();
+            }
             
             // Ocultar welcome webview
             self.welcome_webview.set_visible(false)?;
@@ -648,11 +1119,11 @@ impl WindowManager {
             
             // Se a webview do perfil já existe, apenas mostrar
             if self.profile_webviews.contains_key(uuid) {
-                println!("[WindowManager] Showing existing webview for profile {}", uuid);
+                log::debug!("Showing existing webview for profile {}", uuid);
                 self.profile_webviews.get(uuid).unwrap().set_visible(true)?;
             } else {
                 // Criar nova webview para este perfil
-                println!("[WindowManager] Creating new webview for profile {}", uuid);
+                log::debug!("Creating new webview for profile {}", uuid);
                 
                 let window_size = self.window.inner_size();
                 let window_ptr = &self.window as *const Window;
@@ -660,6 +1131,8 @@ impl WindowManager {
                 #[cfg(target_os = "linux")]
                 let container = self.content_container.clone();
 
+                let state = self.state.clone();
+                let proxy = self.proxy.clone();
                 let web_context = self.get_or_create_web_context(uuid)?;
                 let window_ref = unsafe { &*window_ptr };
                 
@@ -669,139 +1142,141 @@ impl WindowManager {
                     &container,
                     window_size,
                     web_context,
-                    &url,
+                    &profile,
+                    state,
+                    proxy,
                 )?;
                 
                 // Mostrar a nova webview
                 webview.set_visible(true)?;
                 
                 self.profile_webviews.insert(uuid.to_string(), webview);
+                
+                if self.hibernated_profiles.remove(uuid) {
+                    log::info!("Profile {} woke up from hibernation", uuid);
+                    self.update_toolbar_hibernation()?;
+                }
             }
             
             self.current_profile_uuid = Some(uuid.to_string());
             Ok(())
         } else {
-            Err("Perfil não encontrado".into())
+            Err(Error::ProfileNotFound(uuid.to_string()))
         }
     }
 
-    pub fn add_profile(
-        &mut self,
-        name: String,
-        url: String,
-        icon_path: Option<String>,
-        user_agent: Option<String>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let mut profile = crate::profile::WebProfile::new(name.clone(), url.clone(), icon_path.clone(), user_agent);
-        
-        // Se houver ícone, copiar para a pasta do perfil
-        if let Some(path) = &icon_path {
-            match crate::persistence::save_profile_icon(&profile.uuid, path) {
-                Ok(new_path) => {
-                    println!("[WindowManager] Icon saved for new profile: {}", new_path);
-                    profile.icon_path = Some(new_path);
-                }
-                Err(e) => {
-                    eprintln!("[WindowManager] Failed to save icon for new profile: {}", e);
-                }
-            }
-        } else {
-             // No icon provided, try to fetch favicon
-             self.trigger_favicon_fetch(profile.uuid.clone(), url.clone());
+    /// Registra o momento em que o perfil atual deixou de ser exibido
+    fn leave_current_profile(&mut self) {
+        if let Some(uuid) = self.current_profile_uuid.take() {
+            self.last_active.insert(uuid, Instant::now());
         }
+    }
+
+    /// Descarta a webview do perfil mantendo seu WebContext (sessão em disco)
+    fn hibernate_profile(&mut self, uuid: &str) {
+        let allowed = self.state.lock().unwrap().profiles.iter().any(|p| p.uuid == uuid && p.auto_hibernate);
+        if !allowed {
+            log::debug!("Profile {} has hibernation disabled, keeping it loaded", uuid);
+            return;
+        }
+        if let Some(webview) = self.profile_webviews.remove(uuid) {
+            drop(webview);
+            self.hibernated_profiles.insert(uuid.to_string());
+            log::info!("Profile {} hibernated", uuid);
+        }
+    }
+
+    /// Hiberna perfis em background que estão ociosos há mais tempo que o configurado
+    pub fn check_hibernation(&mut self) -> Result<(), Error> {
+        let data = self.state.lock().unwrap();
+        let timeout_minutes = data.settings.hibernate_after_minutes;
         
-        let mut data = self.state.lock().unwrap();
-        data.profiles.push(profile.clone());
-        
-        // Salvar perfis em disco
-        if let Err(e) = crate::persistence::save_profiles(&data.profiles) {
-            eprintln!("[WindowManager] Failed to save profiles: {}", e);
+        if timeout_minutes == 0 {
+            return Ok(());
         }
         
+        // O valor vem de settings.json, que pode ter sido editado à mão
+        let timeout = Duration::from_secs(timeout_minutes.saturating_mul(60));
+        let idle_profiles: Vec<String> = self.profile_webviews.keys()
+            .filter(|uuid| self.current_profile_uuid.as_ref() != Some(*uuid))
+            .filter(|uuid| data.profiles.iter().any(|p| &p.uuid == *uuid && p.auto_hibernate))
+            .filter(|uuid| self.last_active.get(*uuid).is_some_and(|t| t.elapsed() >= timeout))
+            .cloned()
+            .collect();
         drop(data);
         
-        println!("[WindowManager] Profile added: {} ({})", name, url);
+        if idle_profiles.is_empty() {
+            return Ok(());
+        }
         
-        self.update_toolbar_profiles()?;
-        self.show_welcome()?;
+        for uuid in &idle_profiles {
+            self.hibernate_profile(uuid);
+        }
         
-        Ok(())
+        self.update_toolbar_hibernation()
     }
 
-    pub fn update_profile(
-        &mut self,
-        uuid: String,
-        name: String,
-        url: String,
-        icon_path: Option<String>,
-        user_agent: Option<String>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let mut data = self.state.lock().unwrap();
+    /// Traz a janela para frente e exibe o perfil (ex.: clique em notificação)
+    pub fn activate_profile(&mut self, uuid: &str) -> Result<(), Error> {
+        self.window.set_visible(true);
+        self.window.set_minimized(false);
+        self.window.set_focus();
         
-        if let Some(profile) = data.profiles.iter_mut().find(|p| p.uuid == uuid) {
-            profile.name = name.clone();
-            profile.url = url.clone();
-            
-            // Handle Icon update
-            if profile.icon_path != icon_path {
-                // If new icon provided
-                if let Some(path) = &icon_path {
-                    // Only copy if it's a different path (and probably not already in the store, logic simplified here)
-                    // If it is the internal path, we skip.
-                    let is_internal = path.contains(&uuid) && path.contains("feather-alloy");
-                    
-                    if !is_internal {
-                        match crate::persistence::save_profile_icon(&uuid, path) {
-                            Ok(new_path) => {
-                                println!("[WindowManager] Icon updated for profile {}: {}", uuid, new_path);
-                                profile.icon_path = Some(new_path);
-                            }
-                            Err(e) => {
-                                eprintln!("[WindowManager] Failed to save icon: {}", e);
-                                // Fallback to provided path
-                                profile.icon_path = Some(path.clone());
-                            }
-                        }
-                    } else {
-                         profile.icon_path = Some(path.clone());
-                    }
-                } else {
-                    // User removed the icon
-                    if let Err(e) = crate::persistence::delete_profile_icon(&uuid) {
-                        eprintln!("[WindowManager] Failed to delete icon: {}", e);
-                    }
-                    profile.icon_path = None;
-                    // Trigger fetch since we are now without icon
-                    self.trigger_favicon_fetch(uuid.clone(), url.clone());
-                }
-            } else if profile.url != url && profile.icon_path.is_none() {
-                 // URL changed and we are using default/favicon, try to fetch new one
-                 self.trigger_favicon_fetch(uuid.clone(), url.clone());
-            }
-            
-            profile.user_agent = user_agent;
-            
-            // Salvar perfis em disco
-            if let Err(e) = crate::persistence::save_profiles(&data.profiles) {
-                eprintln!("[WindowManager] Failed to save profiles: {}", e);
-            }
-            
-            drop(data);
-            
-            println!("[WindowManager] Profile updated: {} ({})", name, url);
-            
-            self.update_toolbar_profiles()?;
-            self.show_welcome()?;
-            
-            Ok(())
-        } else {
-            drop(data);
-            Err("Perfil não encontrado".into())
+        self.navigate_to_profile(uuid)?;
+        self.send_to_toolbar(&IpcMessage::ProfileActivated { uuid: uuid.to_string() })
+    }
+
+    /// Traz a janela para frente com o foco no último perfil usado
+    fn show_window(&mut self) -> Result<(), Error> {
+        self.window.set_visible(true);
+        self.window.set_minimized(false);
+        self.window.set_focus();
+
+        let last_used = self
+            .current_profile_uuid
+            .clone()
+            .or_else(|| self.last_active.iter().max_by_key(|(_, left)| **left).map(|(uuid, _)| uuid.clone()));
+        let Some(uuid) = last_used else {
+            return Ok(());
+        };
+        if self.current_profile_uuid.as_deref() != Some(uuid.as_str()) {
+            self.navigate_to_profile(&uuid)?;
+        }
+        if let Some(webview) = self.profile_webviews.get(&uuid) {
+            webview.focus()?;
         }
+        Ok(())
     }
 
-    pub fn reload_profile(&mut self, uuid: &str) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(webview) = self.profile_webviews.get(uuid) {
+    /// Exibe uma notificação de desktop vinda de um perfil, respeitando o mute
+    pub fn show_profile_notification(&self, uuid: String, title: String, body: String) -> Result<(), Error> {
+        let data = self.state.lock().unwrap();
+        let Some(profile) = data.profiles.iter().find(|p| p.uuid == uuid) else {
+            return Err(Error::ProfileNotFound(uuid.to_string()));
+        };
+        
+        if profile.notifications_muted {
+            log::info!("Notification from muted profile {} suppressed", profile.name);
+            return Ok(());
+        }
+        
+        let summary = if title.is_empty() {
+            profile.name.clone()
+        } else {
+            format!("{}: {}", profile.name, title)
+        };
+        drop(data);
+        
+        // Uma notificação por perfil: as seguintes substituem a que estiver aberta
+        let key = uuid.clone();
+        let proxy = self.proxy.clone();
+        crate::notifications::show_notification(&key, &summary, &body, move || {
+            let _ = proxy.send_event(AppEvent::ActivateProfile { uuid });
+        })
+    }
+
+    pub fn reload_profile(&mut self, uuid: &str) -> Result<(), Error> {
+        if let Some(webview) = self.profile_webviews.get(uuid) {
             // Recarregar a webview
             let data = self.state.lock().unwrap();
             if let Some(profile) = data.profiles.iter().find(|p| p.uuid == uuid) {
@@ -809,167 +1284,91 @@ impl WindowManager {
                 drop(data);
                 
                 webview.load_url(&url)?;
-                println!("[WindowManager] Profile {} reloaded", uuid);
+                log::info!("Profile {} reloaded", uuid);
                 Ok(())
             } else {
                 drop(data);
-                Err("Perfil não encontrado".into())
+                Err(Error::ProfileNotFound(uuid.to_string()))
             }
         } else {
-            Err("WebView do perfil não encontrada".into())
+            Err(Error::WebView(format!("o perfil {} não está carregado", uuid)))
         }
     }
 
-    pub fn update_profile_icon(&mut self, uuid: &str) -> Result<(), Box<dyn std::error::Error>> {
-        println!("[WindowManager] Update icon for profile {}", uuid);
-        
-        // Obter URL do perfil
-        let data = self.state.lock().unwrap();
-        if let Some(profile) = data.profiles.iter().find(|p| p.uuid == uuid) {
-            let url = profile.url.clone();
-            drop(data);
-            
-            // Trigger fetch
-            self.trigger_favicon_fetch(uuid.to_string(), url);
-        } else {
-            drop(data);
+    /// Exibe o perfil recarregado. Uma webview hibernada ou ainda não aberta já
+    /// é criada com a página nova, então só as existentes são recarregadas.
+    pub fn show_reloaded_profile(&mut self, uuid: &str) -> Result<(), Error> {
+        let loaded = self.profile_webviews.contains_key(uuid);
+        self.navigate_to_profile(uuid)?;
+        if loaded {
+            self.reload_profile(uuid)?;
         }
-        
         Ok(())
     }
 
-    pub fn show_edit_profile(&mut self, uuid: &str) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn show_edit_profile(&mut self, uuid: &str) -> Result<(), Error> {
         let data = self.state.lock().unwrap();
-        
-        if let Some(profile) = data.profiles.iter().find(|p| p.uuid == uuid) {
-            let profile_json = serde_json::to_string(profile)?;
-            drop(data);
-            
-            // Ocultar todas as webviews de perfis
-            for webview in self.profile_webviews.values() {
-                webview.set_visible(false)?;
-            }
-            
-            let edit_profile_html = include_str!("../ui/content/edit-profile.html");
-            
-            // Injetar dados diretamente no HTML antes de carregar
-            // Isso garante que os dados estejam disponíveis imediatamente
-            let html_with_data = edit_profile_html.replace(
-                "</body>",
-                &format!(
-                    r#"<script>
-                    console.log('[EditProfile] Injected data script running');
-                    window.__PROFILE_DATA__ = {};
-                    if (window.loadProfileData) {{
-                        console.log('[EditProfile] Calling loadProfileData immediately');
-                        window.loadProfileData(window.__PROFILE_DATA__);
-                    }} else {{
-                        document.addEventListener('DOMContentLoaded', function() {{
-                            console.log('[EditProfile] DOMContentLoaded, calling loadProfileData');
-                            if (window.loadProfileData) {{
-                                window.loadProfileData(window.__PROFILE_DATA__);
-                            }}
-                        }});
-                    }}
-                    </script></body>"#,
-                    profile_json
-                )
-            );
-            
-            self.welcome_webview.load_html(&html_with_data)?;
-            self.welcome_webview.set_visible(true)?;
-            
-            self.current_profile_uuid = None;
-            println!("[WindowManager] Showing edit profile form for {}", uuid);
-            Ok(())
-        } else {
-            drop(data);
-            Err("Perfil não encontrado".into())
-        }
-    }
-
-    pub fn remove_profile(&mut self, uuid: &str) -> Result<(), Box<dyn std::error::Error>> {
-        // Remover webview se existir
-        if let Some(webview) = self.profile_webviews.remove(uuid) {
-            drop(webview);
-        }
-        
-        // Remover web context
-        self.web_contexts.remove(uuid);
-        
-        // Remover do estado
-        let mut data = self.state.lock().unwrap();
-        data.profiles.retain(|p| p.uuid != uuid);
-        
-        // Salvar perfis em disco
-        if let Err(e) = crate::persistence::save_profiles(&data.profiles) {
-            eprintln!("[WindowManager] Failed to save profiles: {}", e);
-        }
-        
+        let exists = data.profiles.iter().any(|p| p.uuid == uuid);
         drop(data);
         
-        // Limpar dados do perfil do disco
-        if let Err(e) = crate::persistence::delete_profile_data(uuid) {
-            eprintln!("[WindowManager] Failed to delete profile data: {}", e);
+        if !exists {
+            return Err(Error::ProfileNotFound(uuid.to_string()));
         }
         
-        println!("[WindowManager] Profile {} removed", uuid);
+        // Ocultar todas as webviews de perfis
+        for webview in self.profile_webviews.values() {
+            webview.set_visible(false)?;
+        }
         
-        self.update_toolbar_profiles()?;
-        self.show_welcome()?;
+        // A página pede os dados do perfil via IPC (GetProfile)
+        let url = crate::assets::url(&format!("ui/content/edit-profile.html?uuid={}", uuid));
+        self.load_content(&url)?;
+        self.welcome_webview.set_visible(true)?;
         
+        self.leave_current_profile();
+        log::debug!("Showing edit profile form for {}", uuid);
         Ok(())
     }
 
-    pub fn show_settings(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn show_settings(&mut self) -> Result<(), Error> {
         // Ocultar todas as webviews de perfis
         for webview in self.profile_webviews.values() {
             webview.set_visible(false)?;
         }
         
-        // Obter dados das configurações
-        let data = self.state.lock().unwrap();
-        let settings_json = serde_json::to_string(&data.settings)?;
-        drop(data);
-        
-        println!("[WindowManager] Loading settings with data: {}", settings_json);
-        
-        // Carregar HTML e injetar dados diretamente
-        let settings_html = include_str!("../ui/content/settings.html");
-        
-        // Adicionar script de inicialização com os dados no HTML
-        let html_with_data = settings_html.replace(
-            "</body>",
-            &format!(
-                r#"<script>
-                console.log('[Settings] Injected data script running');
-                window.__SETTINGS_DATA__ = {};
-                if (window.loadSettings) {{
-                    console.log('[Settings] Calling loadSettings immediately');
-                    window.loadSettings(window.__SETTINGS_DATA__);
-                }} else {{
-                    console.log('[Settings] loadSettings not ready, will retry');
-                    document.addEventListener('DOMContentLoaded', function() {{
-                        console.log('[Settings] DOMContentLoaded, calling loadSettings');
-                        if (window.loadSettings) {{
-                            window.loadSettings(window.__SETTINGS_DATA__);
-                        }}
-                    }});
-                }}
-                </script></body>"#,
-                settings_json
-            )
-        );
-        
-        self.welcome_webview.load_html(&html_with_data)?;
+        // A página pede as configurações via IPC (GetSettings)
+        self.load_content(&crate::assets::url("ui/content/settings.html"))?;
         self.welcome_webview.set_visible(true)?;
         
-        self.current_profile_uuid = None;
-        println!("[WindowManager] Settings screen loaded");
+        self.leave_current_profile();
+        log::debug!("Settings screen loaded");
         Ok(())
     }
 
-    pub fn select_icon(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    /// Paleta de comandos (Ctrl+K) na área de conteúdo
+    pub fn show_palette(&mut self) -> Result<(), Error> {
+        for webview in self.profile_webviews.values() {
+            webview.set_visible(false)?;
+        }
+
+        // Reabrir a paleta sobre ela mesma mantém o perfil de volta
+        let palette_url = crate::assets::url("ui/content/palette.html");
+        let reopening = self.current_profile_uuid.is_none() && self.welcome_webview.url()? == palette_url;
+        if !reopening {
+            self.palette_return = self.current_profile_uuid.clone();
+        }
+
+        // A página pede os perfis via IPC (GetPalette)
+        self.load_content(&palette_url)?;
+        self.welcome_webview.set_visible(true)?;
+
+        self.leave_current_profile();
+        self.welcome_webview.focus()?;
+        log::debug!("Command palette loaded");
+        Ok(())
+    }
+
+    pub fn select_icon(&mut self) -> Result<(), Error> {
         let proxy = self.proxy.clone();
         
         std::thread::spawn(move || {
@@ -990,27 +1389,396 @@ impl WindowManager {
                     }
                 };
 
-                println!("[WindowManager] Selected icon: {}", abs_path);
+                log::debug!("Selected icon: {}", abs_path);
                 
-                let _ = proxy.send_event(AppEvent::IconSelected { path: abs_path });
+                // O arquivo escolhido fica fora do asset://, então a prévia vai como data: URL
+                let preview = crate::assets::data_url(&path)
+                    .map_err(|e| log::warn!("Failed to read icon preview: {}", e))
+                    .ok();
+                let _ = proxy.send_event(AppEvent::IconSelected { path: abs_path, preview });
+            }
+        });
+
+        Ok(())
+    }
+
+    pub fn select_download_dir(&self) {
+        let proxy = self.proxy.clone();
+        
+        std::thread::spawn(move || {
+            let folder = rfd::FileDialog::new()
+                .set_title("Escolher pasta de downloads")
+                .pick_folder();
+
+            if let Some(path) = folder {
+                log::debug!("Selected download directory: {:?}", path);
+                let _ = proxy.send_event(AppEvent::DownloadDirSelected {
+                    path: path.to_string_lossy().to_string(),
+                });
+            }
+        });
+    }
+
+    /// Pergunta onde salvar e exporta perfis e configurações em segundo plano.
+    /// O pedido `request` é respondido ao final (ver `finish_backup`).
+    pub fn export_data(&self, include_sessions: bool, request: u64) {
+        let data = self.state.lock().unwrap();
+        let profiles = data.profiles.clone();
+        let workspaces = data.workspaces.clone();
+        let settings = data.settings.clone();
+        drop(data);
+        let proxy = self.proxy.clone();
+        let paths = self.paths.clone();
+        
+        std::thread::spawn(move || {
+            let Some(path) = rfd::FileDialog::new()
+                .add_filter("Backup do Feather Alloy", &["zip"])
+                .set_file_name("feather-alloy-backup.zip")
+                .set_title("Exportar perfis")
+                .save_file()
+            else {
+                let _ = proxy.send_event(AppEvent::BackupFinished { request, result: Ok(None) });
+                return;
+            };
+            
+            let result = match crate::persistence::export_archive(&paths, &path, &profiles, &workspaces, &settings, include_sessions) {
+                Ok(()) => Ok(Some(format!("{} perfis exportados para {}", profiles.len(), path.display()))),
+                Err(e) => {
+                    log::error!("Export failed: {}", e);
+                    Err(format!("Falha ao exportar: {}", e))
+                }
+            };
+            let _ = proxy.send_event(AppEvent::BackupFinished { request, result });
+        });
+    }
+
+    pub fn select_import_file(&self, mode: ImportMode, request: u64) {
+        let proxy = self.proxy.clone();
+        
+        std::thread::spawn(move || {
+            let file = rfd::FileDialog::new()
+                .add_filter("Backup do Feather Alloy", &["zip"])
+                .set_title("Importar perfis")
+                .pick_file();
+
+            let event = match file {
+                Some(path) => AppEvent::ImportArchive { path, mode, request },
+                None => AppEvent::BackupFinished { request, result: Ok(None) },
+            };
+            let _ = proxy.send_event(event);
+        });
+    }
+
+    /// Importa um backup. No modo `Replace` os perfis atuais só são removidos
+    /// depois que o arquivo foi lido e extraído com sucesso.
+    pub fn import_data(&mut self, path: &std::path::Path, mode: ImportMode) -> Result<String, Error> {
+        let existing = self.state.lock().unwrap().profiles.clone();
+        let result = crate::persistence::import_archive(&self.paths, path, &existing, mode)?;
+        let imported = result.profiles.len();
+        let mut profiles = result.profiles;
+        
+        // Atalhos, atalho global e log passam pelo controlador, como no formulário
+        if let Some(settings) = result.settings {
+            let commands = self.controller.restore_settings(settings)?;
+            self.apply(commands)?;
+        }
+        
+        let mut data = self.state.lock().unwrap();
+        if mode == ImportMode::Replace {
+            data.workspaces = result.workspaces.unwrap_or_default();
+        }
+        // No modo Merge os workspaces do backup não existem aqui
+        for profile in &mut profiles {
+            profile.workspaces.retain(|workspace| data.workspaces.exists(workspace));
+        }
+        match mode {
+            ImportMode::Merge => data.profiles.extend(profiles),
+            ImportMode::Replace => data.profiles = profiles,
+        }
+        if let Err(e) = crate::persistence::save_profiles(&self.paths, &data.profiles) {
+            log::error!("Failed to save profiles: {}", e);
+        }
+        if let Err(e) = crate::persistence::save_workspaces(&self.paths, &data.workspaces) {
+            log::error!("Failed to save workspaces: {}", e);
+        }
+        drop(data);
+        
+        if mode == ImportMode::Replace {
+            for profile in &existing {
+                self.profile_webviews.remove(&profile.uuid);
+                self.web_contexts.remove(&profile.uuid);
+                self.last_active.remove(&profile.uuid);
+                self.hibernated_profiles.remove(&profile.uuid);
+                self.unread_counts.remove(&profile.uuid);
+                if let Err(e) = crate::persistence::delete_profile_data(&self.paths, &profile.uuid) {
+                    log::error!("Failed to delete profile data: {}", e);
+                }
             }
+            if self.current_profile_uuid.is_some() {
+                self.show_welcome()?;
+            }
+            
+            // Atualizar o formulário de configurações aberto com os valores importados
+            self.send_settings_data()?;
+            self.publish_workspaces()?;
+            self.update_toolbar_hibernation()?;
+            self.publish_unread_counts()?;
+        }
+        
+        self.update_toolbar_profiles()?;
+        self.update_tray_menu()?;
+        
+        let mut message = format!("{} perfis importados", imported);
+        if result.remapped > 0 {
+            message.push_str(&format!(" ({} com novo identificador)", result.remapped));
+        }
+        Ok(message)
+    }
+
+    /// Pergunta pela pasta de dados do Ferdium (sugerindo a instalação local)
+    pub fn select_ferdium_dir(&self, copy_sessions: bool, request: u64) {
+        let proxy = self.proxy.clone();
+        
+        std::thread::spawn(move || {
+            let mut dialog = rfd::FileDialog::new().set_title("Pasta de dados do Ferdium");
+            if let Some(dir) = crate::ferdium::default_data_dir() {
+                dialog = dialog.set_directory(dir);
+            }
+
+            let event = match dialog.pick_folder() {
+                Some(path) => AppEvent::ImportFerdiumDir { path, copy_sessions, request },
+                None => AppEvent::BackupFinished { request, result: Ok(None) },
+            };
+            let _ = proxy.send_event(event);
         });
+    }
+
+    /// Adiciona os serviços do Ferdium como novos perfis
+    pub fn import_ferdium(&mut self, path: &std::path::Path, copy_sessions: bool) -> Result<String, Error> {
+        let result = crate::ferdium::import_services(path, self.paths.data_dir(), copy_sessions)?;
+        let imported = result.profiles.len();
+        
+        let mut data = self.state.lock().unwrap();
+        for profile in &result.profiles {
+            if profile.icon_path.is_none() {
+                self.trigger_favicon_fetch(profile.uuid.clone(), profile.url.clone());
+            }
+        }
+        data.profiles.extend(result.profiles);
+        if let Err(e) = crate::persistence::save_profiles(&self.paths, &data.profiles) {
+            log::error!("Failed to save profiles: {}", e);
+        }
+        drop(data);
+        
+        self.update_toolbar_profiles()?;
+        self.update_tray_menu()?;
+        
+        let mut message = format!("{} serviços importados do Ferdium", imported);
+        if copy_sessions {
+            message.push_str(&format!(", {} com sessão copiada", result.sessions_copied));
+        }
+        if !result.skipped.is_empty() {
+            message.push_str(&format!(" (ignorados, sem URL: {})", result.skipped.join(", ")));
+        }
+        Ok(message)
+    }
 
+    pub fn show_downloads(&mut self) -> Result<(), Error> {
+        // Ocultar todas as webviews de perfis
+        for webview in self.profile_webviews.values() {
+            webview.set_visible(false)?;
+        }
+        
+        self.load_content(&crate::assets::url("ui/content/downloads.html"))?;
+        self.welcome_webview.set_visible(true)?;
+        
+        self.leave_current_profile();
+        log::debug!("Downloads screen loaded");
         Ok(())
     }
 
+    /// Atualiza a lista da página de downloads, se ela estiver aberta
+    fn refresh_downloads_page(&self) -> Result<(), Error> {
+        self.call_content("loadDownloads", &self.downloads.entries())
+    }
+
+    /// Chama `window.{function}(data)` na página de conteúdo, se ela definir a função
+    fn call_content<T: serde::Serialize + ?Sized>(&self, function: &str, data: &T) -> Result<(), Error> {
+        let script = format!(
+            "if (window.{0}) {{ window.{0}({1}); }}",
+            function,
+            serde_json::to_string(data)?
+        );
+        self.welcome_webview.evaluate_script(&script)?;
+        Ok(())
+    }
+
+    /// Atende uma chamada `backend.call()` e resolve a Promise na webview de origem
+    fn handle_ipc_request(&mut self, source: IpcSource, request: IpcRequest) {
+        let IpcRequest { id, message } = request;
+        // Backups esperam o seletor de arquivos em outra thread e respondem em `finish_backup`
+        match message {
+            IpcMessage::ExportData { include_sessions } => return self.export_data(include_sessions, id),
+            IpcMessage::ImportData { mode } => return self.select_import_file(mode, id),
+            IpcMessage::ImportFerdium { copy_sessions } => return self.select_ferdium_dir(copy_sessions, id),
+            _ => {}
+        }
+
+        let response = IpcResponse::new(id, self.handle_request(message));
+        if let Some(error) = &response.error {
+            log::warn!("Request #{} failed: {}", id, error);
+        }
+        self.send_response(source, response);
+    }
+
+    /// Responde a um pedido de exportação ou importação (vindos sempre da página de configurações)
+    fn finish_backup(&self, request: u64, result: Result<Option<String>, String>) {
+        let result = result.map(|message| message.map_or(serde_json::Value::Null, serde_json::Value::String));
+        self.send_response(IpcSource::Content, IpcResponse::from_result(request, result));
+    }
+
+    fn send_response(&self, source: IpcSource, response: IpcResponse) {
+        let webview = match source {
+            IpcSource::Toolbar => &self.toolbar_webview,
+            IpcSource::Content => &self.welcome_webview,
+            // Perfis não fazem requisições com resposta
+            IpcSource::Profile => return,
+        };
+        let result = response
+            .to_script()
+            .map_err(|e| e.to_string())
+            .and_then(|script| webview.evaluate_script(&script).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            log::warn!("Failed to deliver response #{}: {}", response.id, e);
+        }
+    }
+
+    /// Toolbar e conteúdo só exibem a interface embutida: links para sites
+    /// abrem no navegador do sistema e o resto é bloqueado
+    fn ui_navigation_handler(url: String) -> bool {
+        if url.parse::<http::Uri>().is_ok_and(|uri| crate::assets::is_app_uri(&uri)) {
+            return true;
+        }
+        
+        if url.starts_with("http://") || url.starts_with("https://") {
+            if let Err(e) = crate::links::open_external(&url) {
+                log::warn!("Failed to open external link {}: {}", url, e);
+            }
+        } else {
+            log::warn!("Blocked navigation of app UI to {}", url);
+        }
+        false
+    }
+
+    /// Alterações feitas via `backend.call()`: a Promise é resolvida com `null`
+    /// depois que os comandos foram aplicados, ou rejeitada com o erro
+    fn handle_request(&mut self, message: IpcMessage) -> Result<serde_json::Value, Error> {
+        let commands = match message {
+            IpcMessage::AddProfile { name, url, icon_path, user_agent } => {
+                self.controller.add_profile(name, url, icon_path, user_agent)
+            }
+            IpcMessage::UpdateProfile { uuid, changes } => self.controller.update_profile(&uuid, changes)?,
+            IpcMessage::ReorderProfiles { uuids } => self.controller.reorder_profiles(&uuids),
+            IpcMessage::SwitchWorkspace { uuid } => {
+                let current = self.current_profile_uuid.clone();
+                self.controller.switch_workspace(uuid, current.as_deref())?
+            }
+            IpcMessage::AddWorkspace { name } => self.controller.add_workspace(&name)?,
+            IpcMessage::RenameWorkspace { uuid, name } => self.controller.rename_workspace(&uuid, &name)?,
+            IpcMessage::RemoveWorkspace { uuid } => self.controller.remove_workspace(&uuid)?,
+            IpcMessage::UpdateSettings { settings } => self.controller.update_settings(settings)?,
+            query => return self.handle_query(query),
+        };
+        self.apply(commands)?;
+        Ok(serde_json::Value::Null)
+    }
+
+    /// Consultas disponíveis via `backend.call()`
+    fn handle_query(&self, message: IpcMessage) -> Result<serde_json::Value, Error> {
+        let data = self.state.lock().unwrap();
+        let value = match message {
+            IpcMessage::GetProfiles => serde_json::to_value(&data.profiles)?,
+            IpcMessage::GetProfile { uuid } => {
+                let profile = data.profiles.iter().find(|p| p.uuid == uuid).ok_or_else(|| Error::ProfileNotFound(uuid.clone()))?;
+                serde_json::to_value(profile)?
+            }
+            IpcMessage::GetWorkspaces => serde_json::to_value(&data.workspaces)?,
+            IpcMessage::GetSettings => serde_json::to_value(&data.settings)?,
+            IpcMessage::GetDownloads => serde_json::to_value(self.downloads.entries())?,
+            IpcMessage::GetPalette => {
+                drop(data);
+                serde_json::json!({
+                    "profiles": self.controller.palette_profiles(&self.last_active),
+                    "previous": self.palette_return,
+                })
+            }
+            other => return Err(Error::Ipc(format!("requisição não suportada: {:?}", other))),
+        };
+        Ok(value)
+    }
+
+    fn send_settings_data(&self) -> Result<(), Error> {
+        let settings = self.state.lock().unwrap().settings.clone();
+        self.call_content("loadSettings", &settings)
+    }
+
+    fn handle_download_started(&mut self, uuid: &str, url: &str, path: &std::path::Path) {
+        let profile_name = self.profile_name(uuid);
+        let id = self.downloads.start(uuid, &profile_name, url, path);
+        log::info!("Download #{} started for profile {}: {:?}", id, uuid, path);
+        let _ = self.refresh_downloads_page();
+    }
+
+    fn handle_download_finished(&mut self, uuid: &str, url: &str, path: Option<PathBuf>, success: bool) {
+        let Some(entry) = self.downloads.finish(uuid, url, path, success).cloned() else {
+            return;
+        };
+        log::info!("Download #{} finished (success: {}): {}", entry.id, success, entry.path);
+        let _ = self.refresh_downloads_page();
+        
+        let (summary, body) = if success {
+            ("Download concluído", format!("{} ({})", entry.file_name, entry.profile_name))
+        } else {
+            ("Falha no download", format!("{} ({})", entry.file_name, entry.profile_name))
+        };
+        let proxy = self.proxy.clone();
+        let id = entry.id;
+        let result = crate::notifications::show_notification(&format!("download-{}", id), summary, &body, move || {
+            let _ = proxy.send_event(AppEvent::ShowDownloadInFolder { id });
+        });
+        if let Err(e) = result {
+            log::warn!("Failed to show download notification: {}", e);
+        }
+    }
+
+    /// Abre o arquivo baixado, ou a pasta que o contém
+    fn open_download(&self, id: u64, show_folder: bool) {
+        let Some(entry) = self.downloads.get(id) else {
+            return;
+        };
+        let path = std::path::Path::new(&entry.path);
+        let target = if show_folder { path.parent().unwrap_or(path) } else { path };
+        if let Err(e) = crate::links::open_external(&target.to_string_lossy()) {
+            self.show_error(format!("Não foi possível abrir {}: {}", target.display(), e));
+        }
+    }
+
+    fn profile_name(&self, uuid: &str) -> String {
+        let data = self.state.lock().unwrap();
+        data.profiles
+            .iter()
+            .find(|p| p.uuid == uuid)
+            .map(|p| p.name.clone())
+            .unwrap_or_default()
+    }
+
     pub fn trigger_favicon_fetch(&self, uuid: String, url: String) {
         let proxy = self.proxy.clone();
+        let profile_dir = self.paths.profile_dir(&uuid);
         std::thread::spawn(move || {
-            println!("[WindowManager] Fetching favicon for {}", url);
+            log::debug!("Fetching favicon for {}", url);
             match crate::favicon::fetch_favicon(&url) {
                 Ok(result) => {
-                    let profile_dir = dirs::data_dir()
-                        .unwrap() // Safe enough
-                        .join("feather-alloy")
-                        .join("profiles")
-                        .join(&uuid);
-                        
                     std::fs::create_dir_all(&profile_dir).unwrap();
                     let filename = format!("favicon.{}", result.extension);
                     let path = profile_dir.join(&filename);
@@ -1024,59 +1792,91 @@ impl WindowManager {
                     }
                 }
                 Err(e) => {
-                    eprintln!("[WindowManager] Error fetching favicon: {}", e);
+                    log::warn!("Error fetching favicon: {}", e);
+                    let _ = proxy.send_event(AppEvent::ShowError {
+                        message: format!("Não foi possível obter o ícone de {}: {}", url, e),
+                    });
                 }
             }
         });
     }
 
-    pub fn handle_favicon_fetched(&mut self, uuid: String, path: String) -> Result<(), Box<dyn std::error::Error>> {
-        let mut data = self.state.lock().unwrap();
-        if let Some(profile) = data.profiles.iter_mut().find(|p| p.uuid == uuid) {
-            // Only update if user hasn't set a custom icon in the meantime?
-            // For now, simple: Just update.
-            profile.icon_path = Some(path.clone());
-            println!("[WindowManager] Favicon applied for {}: {}", uuid, path);
-            
-            // Save
-            if let Err(e) = crate::persistence::save_profiles(&data.profiles) {
-                eprintln!("[WindowManager] Failed to save profiles after favicon update: {}", e);
+    /// Executa os comandos de interface gerados pelo `AppController`
+    fn apply(&mut self, commands: Vec<ViewCommand>) -> Result<(), Error> {
+        for command in commands {
+            match command {
+                ViewCommand::RefreshToolbar => {
+                    self.update_toolbar_profiles()?;
+                    self.update_tray_menu()?;
+                }
+                ViewCommand::RefreshWorkspaces => self.publish_workspaces()?,
+                ViewCommand::ShowWelcome => self.show_welcome()?,
+                ViewCommand::RebuildProfileView { uuid } => {
+                    // O WebContext é mantido; a webview é recriada na próxima navegação
+                    if self.profile_webviews.remove(&uuid).is_some() {
+                        log::info!("Webview settings changed, webview for profile {} will be rebuilt", uuid);
+                    }
+                }
+                ViewCommand::ForgetProfile { uuid } => {
+                    self.profile_webviews.remove(&uuid);
+                    self.web_contexts.remove(&uuid);
+                    self.last_active.remove(&uuid);
+                    if self.hibernated_profiles.remove(&uuid) {
+                        self.update_toolbar_hibernation()?;
+                    }
+                    if self.unread_counts.remove(&uuid).is_some() {
+                        self.publish_unread_counts()?;
+                    }
+                }
+                ViewCommand::FetchFavicon { uuid, url } => self.trigger_favicon_fetch(uuid, url),
+                ViewCommand::HibernateProfiles(uuids) => {
+                    for uuid in &uuids {
+                        self.hibernate_profile(uuid);
+                    }
+                    self.update_toolbar_hibernation()?;
+                }
+                ViewCommand::ActivateProfile { uuid } => self.activate_profile(&uuid)?,
+                ViewCommand::ReloadProfile { uuid } => self.reload_profile(&uuid)?,
+                ViewCommand::ShowSettings => self.show_settings()?,
+                ViewCommand::ShowPalette => self.show_palette()?,
+                ViewCommand::RefreshShortcuts => self.publish_shortcuts()?,
+                ViewCommand::RegisterGlobalHotkey => self.register_global_hotkey()?,
+                ViewCommand::SetVerboseLogging(verbose) => crate::logging::set_verbose(verbose),
+                ViewCommand::ShowError(message) => self.show_error(message),
+                ViewCommand::ShowNotice(message) => self.show_notice(message),
             }
-            drop(data);
-            
-            self.update_toolbar_profiles()?;
         }
         Ok(())
     }
 
-    pub fn update_settings(
-        &mut self,
-        minimize_on_open: bool,
-        minimize_on_close: bool,
-        hide_on_close: bool,
-        enable_tray: bool,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let mut data = self.state.lock().unwrap();
-        data.settings.minimize_on_open = minimize_on_open;
-        data.settings.minimize_on_close = minimize_on_close;
-        data.settings.hide_on_close = hide_on_close;
-        data.settings.enable_tray = enable_tray;
-        
-        // Salvar configurações em disco
-        if let Err(e) = crate::persistence::save_settings(&data.settings) {
-            eprintln!("[WindowManager] Failed to save settings: {}", e);
-        }
-        
-        drop(data);
+    /// Recalcula as não lidas do perfil a partir do novo título da página
+    pub fn handle_title_changed(&mut self, uuid: String, title: String) -> Result<(), Error> {
+        let Some(count) = self.controller.unread_count(&uuid, &title) else {
+            return Ok(());
+        };
         
-        println!("[WindowManager] Settings updated");
+        let previous = self.unread_counts.get(&uuid).copied().unwrap_or(0);
+        if count == previous {
+            return Ok(());
+        }
         
-        self.show_welcome()?;
+        log::debug!("Unread count for {}: {} -> {}", uuid, previous, count);
+        if count == 0 {
+            self.unread_counts.remove(&uuid);
+        } else {
+            self.unread_counts.insert(uuid, count);
+        }
         
-        Ok(())
+        self.publish_unread_counts()
     }
 
-    pub fn update_toolbar_profiles(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    /// Propaga as contagens de não lidas para a toolbar e para o tray
+    fn publish_unread_counts(&self) -> Result<(), Error> {
+        self.update_toolbar_unread()?;
+        self.update_tray_unread()
+    }
+
+    pub fn update_toolbar_profiles(&mut self) -> Result<(), Error> {
         let data = self.state.lock().unwrap();
         let profiles_json = serde_json::to_string(&data.profiles)?;
         drop(data);
@@ -1087,10 +1887,115 @@ impl WindowManager {
         );
         
         self.toolbar_webview.evaluate_script(&script)?;
-        println!("[WindowManager] Toolbar profiles updated");
+        log::debug!("Toolbar profiles updated");
+        Ok(())
+    }
+
+    /// Define as combinações interceptadas pelo script de atalhos (ui/shortcuts.js)
+    fn shortcut_bindings_script(shortcuts: &Shortcuts) -> String {
+        format!("window.__featherShortcuts = {};", serde_json::json!(shortcuts.bindings()))
+    }
+
+    /// Reenvia os atalhos para as webviews abertas após uma mudança nas configurações
+    fn publish_shortcuts(&self) -> Result<(), Error> {
+        let script = Self::shortcut_bindings_script(&self.state.lock().unwrap().settings.shortcuts);
+        self.toolbar_webview.evaluate_script(&script)?;
+        self.welcome_webview.evaluate_script(&script)?;
+        for webview in self.profile_webviews.values() {
+            webview.evaluate_script(&script)?;
+        }
+        Ok(())
+    }
+
+    /// Envia os workspaces para o seletor da toolbar e para a página de configurações
+    fn publish_workspaces(&self) -> Result<(), Error> {
+        let workspaces = self.state.lock().unwrap().workspaces.clone();
+        let message = IpcMessage::WorkspacesUpdated { workspaces };
+        self.send_to_toolbar(&message)?;
+        Self::post_message(&self.welcome_webview, &message)
+    }
+
+    /// Envia os perfis hibernados para a toolbar exibir o estado "dormindo"
+    pub fn update_toolbar_hibernation(&self) -> Result<(), Error> {
+        let hibernated = self.hibernated_profiles.iter().cloned().collect();
+        self.send_to_toolbar(&IpcMessage::HibernationChanged { hibernated })
+    }
+
+    /// Envia as contagens de não lidas para os badges da toolbar
+    pub fn update_toolbar_unread(&self) -> Result<(), Error> {
+        self.send_to_toolbar(&IpcMessage::UnreadCountsUpdated {
+            counts: self.unread_counts.clone(),
+        })
+    }
+
+    /// Entrega uma mensagem do backend ao listener 'message' da toolbar
+    fn send_to_toolbar(&self, message: &IpcMessage) -> Result<(), Error> {
+        Self::post_message(&self.toolbar_webview, message)
+    }
+
+    fn post_message(webview: &WebView, message: &IpcMessage) -> Result<(), Error> {
+        let json = message.to_json()?;
+        let script = format!("window.postMessage({}, '*');", serde_json::to_string(&json)?);
+        
+        webview.evaluate_script(&script)?;
+        Ok(())
+    }
+
+    /// Carrega uma página na webview de conteúdo. Avisos enviados durante o
+    /// carregamento são guardados e entregues à nova página.
+    fn load_content(&self, url: &str) -> Result<(), Error> {
+        self.content_loading.set(true);
+        self.welcome_webview.load_url(url)?;
         Ok(())
     }
 
+    fn handle_content_loaded(&self) {
+        self.content_loading.set(false);
+        // Os atalhos do script de inicialização podem ter mudado desde a criação da webview
+        let script = Self::shortcut_bindings_script(&self.state.lock().unwrap().settings.shortcuts);
+        if let Err(e) = self.welcome_webview.evaluate_script(&script) {
+            log::warn!("Failed to refresh shortcuts for content page: {}", e);
+        }
+        let pending = self.pending_notices.take();
+        for message in &pending {
+            self.deliver_notice(message);
+        }
+    }
+
+    /// Exibe um erro para o usuário como toast
+    pub fn show_error(&self, message: impl Into<String>) {
+        let message = message.into();
+        log::error!("Shown to user: {}", message);
+        self.deliver_notice(&IpcMessage::Error { message });
+    }
+
+    /// Exibe um aviso informativo como toast
+    pub fn show_notice(&self, message: impl Into<String>) {
+        self.deliver_notice(&IpcMessage::Notice { message: message.into() });
+    }
+
+    /// Relata a falha de uma ação ao usuário, precedida do contexto
+    fn report<T>(&self, context: &str, result: Result<T, Error>) {
+        if let Err(e) = result {
+            self.show_error(format!("{}: {}", context, e));
+        }
+    }
+
+    /// Toasts aparecem na página de conteúdo; com um perfil aberto, na toolbar
+    fn deliver_notice(&self, message: &IpcMessage) {
+        let webview = if self.current_profile_uuid.is_some() {
+            &self.toolbar_webview
+        } else if self.content_loading.get() {
+            self.pending_notices.borrow_mut().push(message.clone());
+            return;
+        } else {
+            &self.welcome_webview
+        };
+        if let Err(e) = Self::post_message(webview, message) {
+            log::warn!("Failed to deliver notice {:?}: {}", message, e);
+        }
+    }
+
     pub fn handle_resize(&mut self, new_size: PhysicalSize<u32>) {
         let toolbar_bounds = Rect {
             position: PhysicalPosition::new(0, 0).into(),
@@ -1162,77 +2067,207 @@ impl WindowManager {
 
             match event {
                 Event::UserEvent(app_event) => {
-                    println!("[WindowManager] >>> RECEIVED USER EVENT: {:?}", app_event);
+                    log::debug!("Received user event: {:?}", app_event);
                     match app_event {
                         AppEvent::ShowAddProfileForm => {
-                            let _ = self.show_add_profile_form();
+                            let result = self.show_add_profile_form();
+                            self.report("Não foi possível abrir o formulário", result);
                         }
                         AppEvent::CancelAddProfile => {
-                            let _ = self.show_welcome();
+                            let result = self.show_welcome();
+                            self.report("Não foi possível abrir a tela inicial", result);
+                        }
+                        AppEvent::ShowProfile { uuid } => {
+                            let result = self.navigate_to_profile(&uuid);
+                            self.report("Não foi possível abrir o perfil", result);
                         }
-                        AppEvent::AddProfile { name, url, icon_path, user_agent } => {
-                            let _ = self.add_profile(name, url, icon_path, user_agent);
+                        AppEvent::ShowReloadedProfile { uuid } => {
+                            let result = self.show_reloaded_profile(&uuid);
+                            self.report("Não foi possível recarregar o perfil", result);
                         }
-                        AppEvent::UpdateProfile { uuid, name, url, icon_path, user_agent } => {
-                            let _ = self.update_profile(uuid, name, url, icon_path, user_agent);
+                        AppEvent::ActivateProfile { uuid } => {
+                            let result = self.activate_profile(&uuid);
+                            self.report("Não foi possível abrir o perfil", result);
                         }
-                        AppEvent::ShowProfile { uuid } => {
-                            let _ = self.navigate_to_profile(&uuid);
+                        AppEvent::ShowNotification { uuid, title, body } => {
+                            let result = self.show_profile_notification(uuid, title, body);
+                            self.report("Não foi possível exibir a notificação", result);
                         }
                         AppEvent::ReloadProfile { uuid } => {
-                            let _ = self.reload_profile(&uuid);
+                            let result = self.reload_profile(&uuid);
+                            self.report("Não foi possível recarregar o perfil", result);
                         }
                         AppEvent::UpdateProfileIcon { uuid } => {
-                            let _ = self.update_profile_icon(&uuid);
+                            let result = self
+                                .controller
+                                .refresh_profile_icon(&uuid)
+                                .and_then(|commands| self.apply(commands));
+                            self.report("Não foi possível atualizar o ícone", result);
                         }
                         AppEvent::ShowEditProfile { uuid } => {
-                            let _ = self.show_edit_profile(&uuid);
+                            let result = self.show_edit_profile(&uuid);
+                            self.report("Não foi possível abrir a edição do perfil", result);
                         }
                         AppEvent::RemoveProfile { uuid } => {
-                            let _ = self.remove_profile(&uuid);
+                            let result = self
+                                .controller
+                                .remove_profile(&uuid)
+                                .and_then(|commands| self.apply(commands));
+                            self.report("Não foi possível remover o perfil", result);
                         }
                         AppEvent::UpdateToolbar => {
-                            let _ = self.update_toolbar_profiles();
+                            let result = self.update_toolbar_profiles();
+                            self.report("Não foi possível atualizar a barra lateral", result);
                         }
                         AppEvent::ShowWelcome => {
-                            let _ = self.show_welcome();
+                            let result = self.show_welcome();
+                            self.report("Não foi possível abrir a tela inicial", result);
                         }
                         AppEvent::ShowSettings => {
-                            let _ = self.show_settings();
+                            let result = self.show_settings();
+                            self.report("Não foi possível abrir as configurações", result);
                         }
-                        AppEvent::UpdateSettings { minimize_on_open, minimize_on_close, hide_on_close, enable_tray } => {
-                            let _ = self.update_settings(minimize_on_open, minimize_on_close, hide_on_close, enable_tray);
+                        AppEvent::SelectDownloadDir => {
+                            self.select_download_dir();
                         }
-                        AppEvent::SelectIcon => {
-                            if let Err(e) = self.select_icon() {
-                                eprintln!("[WindowManager] Failed to select icon: {}", e);
+                        AppEvent::DownloadDirSelected { path } => {
+                            let result = self.call_content("updateDownloadDir", &path);
+                            self.report("Não foi possível atualizar a pasta de downloads", result);
+                        }
+                        AppEvent::ImportArchive { path, mode, request } => {
+                            let result = self.import_data(&path, mode).map(Some).map_err(|e| {
+                                log::error!("Import failed: {}", e);
+                                format!("Falha ao importar: {}", e)
+                            });
+                            self.finish_backup(request, result);
+                        }
+                        AppEvent::ImportFerdiumDir { path, copy_sessions, request } => {
+                            let result = self.import_ferdium(&path, copy_sessions).map(Some).map_err(|e| {
+                                log::error!("Ferdium import failed: {}", e);
+                                format!("Falha ao importar do Ferdium: {}", e)
+                            });
+                            self.finish_backup(request, result);
+                        }
+                        AppEvent::BackupFinished { request, result } => {
+                            self.finish_backup(request, result);
+                        }
+                        AppEvent::IpcRequest { source, request } => {
+                            self.handle_ipc_request(source, request);
+                        }
+                        AppEvent::IpcRejected { source, id, reason } => {
+                            self.send_response(source, IpcResponse::new(id, Err(Error::Ipc(reason))));
+                        }
+                        AppEvent::ShowError { message } => {
+                            self.show_error(message);
+                        }
+                        AppEvent::ContentLoaded => {
+                            self.handle_content_loaded();
+                        }
+                        AppEvent::ProfileLoaded { uuid } => {
+                            if let Some(webview) = self.profile_webviews.get(&uuid) {
+                                let shortcuts = self.state.lock().unwrap().settings.shortcuts.clone();
+                                let result = webview.evaluate_script(&Self::shortcut_bindings_script(&shortcuts));
+                                if let Err(e) = result {
+                                    log::warn!("Failed to refresh shortcuts for profile {}: {}", uuid, e);
+                                }
                             }
                         }
+                        AppEvent::Shortcut { combo } => {
+                            let current = self.current_profile_uuid.clone();
+                            let commands = self.controller.handle_shortcut(&combo, current.as_deref());
+                            let result = self.apply(commands);
+                            self.report("Não foi possível executar o atalho", result);
+                        }
+                        AppEvent::ShowDownloads => {
+                            let result = self.show_downloads();
+                            self.report("Não foi possível abrir os downloads", result);
+                        }
+                        AppEvent::RefreshDownloads => {
+                            let result = self.refresh_downloads_page();
+                            self.report("Não foi possível atualizar os downloads", result);
+                        }
+                        AppEvent::DownloadStarted { uuid, url, path } => {
+                            self.handle_download_started(&uuid, &url, &path);
+                        }
+                        AppEvent::DownloadFinished { uuid, url, path, success } => {
+                            self.handle_download_finished(&uuid, &url, path, success);
+                        }
+                        AppEvent::OpenDownload { id } => {
+                            self.open_download(id, false);
+                        }
+                        AppEvent::ShowDownloadInFolder { id } => {
+                            self.open_download(id, true);
+                        }
+                        AppEvent::OpenDownloadsFolder => {
+                            let dir = crate::downloads::download_directory(&self.state.lock().unwrap().settings);
+                            let result = crate::links::open_external(&dir.to_string_lossy());
+                            self.report("Não foi possível abrir a pasta de downloads", result.map_err(Into::into));
+                        }
+                        AppEvent::OpenLogs => {
+                            let dir = self.paths.logs_dir();
+                            let result = std::fs::create_dir_all(&dir)
+                                .and_then(|_| crate::links::open_external(&dir.to_string_lossy()));
+                            self.report("Não foi possível abrir a pasta de logs", result.map_err(Into::into));
+                        }
+                        AppEvent::ClearDownloads => {
+                            self.downloads.clear_finished();
+                            let result = self.refresh_downloads_page();
+                            self.report("Não foi possível atualizar os downloads", result);
+                        }
+                        AppEvent::CheckHibernation => {
+                            let result = self.check_hibernation();
+                            self.report("Falha ao hibernar perfis inativos", result);
+                        }
+                        AppEvent::SelectIcon => {
+                            let result = self.select_icon();
+                            self.report("Não foi possível selecionar o ícone", result);
+                        }
+                        AppEvent::TitleChanged { uuid, title } => {
+                            let result = self.handle_title_changed(uuid, title);
+                            self.report("Não foi possível atualizar as não lidas", result);
+                        }
                         AppEvent::FaviconFetched { uuid, path } => {
-                            let _ = self.handle_favicon_fetched(uuid, path);
+                            let commands = self.controller.apply_favicon(&uuid, path);
+                            let result = self.apply(commands);
+                            self.report("Não foi possível aplicar o ícone", result);
                         }
-                        AppEvent::IconSelected { path } => {
-                             let script = format!("window.updateSelectedIcon('{}')", path.replace("\\", "\\\\").replace("'", "\\'"));
-                             if let Err(e) = self.welcome_webview.evaluate_script(&script) {
-                                 eprintln!("[WindowManager] Failed to update selected icon in UI: {}", e);
-                             }
+                        AppEvent::IconSelected { path, preview } => {
+                             let script = format!(
+                                 "window.updateSelectedIcon({}, {})",
+                                 serde_json::to_string(&path).unwrap_or_default(),
+                                 serde_json::to_string(&preview).unwrap_or_default()
+                             );
+                             let result = self.welcome_webview.evaluate_script(&script);
+                             self.report("Não foi possível exibir o ícone selecionado", result.map_err(Into::into));
                         }
                         AppEvent::ToggleWindow => {
-                            println!("[WindowManager] >>> TOGGLE WINDOW EVENT");
-                            let is_visible = self.window.is_visible();
-                            self.window.set_visible(!is_visible);
-                            if !is_visible { 
-                                self.window.set_focus(); 
+                            log::info!("Toggle window requested");
+                            if self.window.is_visible() && !self.window.is_minimized() {
+                                self.window.set_visible(false);
+                            } else {
+                                let result = self.show_window();
+                                self.report("Não foi possível abrir o perfil", result);
+                            }
+                        }
+                        AppEvent::SummonWindow => {
+                            log::info!("Summon window requested");
+                            // Aberta atrás de outras janelas: traz para frente em vez de ocultar
+                            if self.window.is_visible() && self.window.is_focused() {
+                                self.window.set_visible(false);
+                            } else {
+                                let result = self.show_window();
+                                self.report("Não foi possível abrir o perfil", result);
                             }
                         }
                         AppEvent::ShowProfileContextMenu { uuid, x, y } => {
                             self.context_menu_target_uuid = Some(uuid);
                             if let Some(context_menu) = &self.context_menu {
-                                let _ = context_menu.show_at(&self.window, x, y);
+                                let result = context_menu.show_at(&self.window, x, y);
+                                self.report("Não foi possível abrir o menu do perfil", result);
                             }
                         }
                         AppEvent::Quit => {
-                            println!("[WindowManager] >>> QUIT EVENT");
+                            log::info!("Quit requested");
                             *control_flow = ControlFlow::Exit;
                         }
                     }
@@ -1241,14 +2276,10 @@ impl WindowManager {
                     event: WindowEvent::CloseRequested,
                     ..
                 } => {
-                    let settings = self.state.lock().unwrap().settings.clone();
-                    
-                    if settings.hide_on_close {
-                        self.window.set_visible(false);
-                    } else if settings.minimize_on_close {
-                        self.window.set_minimized(true);
-                    } else {
-                        *control_flow = ControlFlow::Exit;
+                    match self.controller.close_action() {
+                        CloseAction::Hide => self.window.set_visible(false),
+                        CloseAction::Minimize => self.window.set_minimized(true),
+                        CloseAction::Exit => *control_flow = ControlFlow::Exit,
                     }
                 }
                 Event::WindowEvent {
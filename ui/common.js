@@ -0,0 +1,126 @@
+// Utilitários compartilhados pela toolbar e pelas páginas de conteúdo
+
+function sendMessage(message) {
+    if (window.ipc) {
+        window.ipc.postMessage(JSON.stringify(message));
+    } else {
+        console.error('[UI] IPC not available');
+    }
+}
+
+// Chamadas com resposta: cada pedido leva um id e recebe uma Promise, resolvida
+// quando o backend chama backend.resolve({ id, ok, data, error })
+window.backend = (() => {
+    // Ids partem do horário de carregamento para que respostas atrasadas
+    // destinadas à página anterior não coincidam com pedidos desta
+    let nextId = Date.now();
+    const pending = new Map();
+
+    return {
+        call(type, payload) {
+            if (!window.ipc) {
+                return Promise.reject(new Error('IPC not available'));
+            }
+            const id = ++nextId;
+            const message = payload === undefined ? { type } : { type, payload };
+            return new Promise((resolve, reject) => {
+                pending.set(id, { resolve, reject });
+                window.ipc.postMessage(JSON.stringify({ id, message }));
+            });
+        },
+
+        resolve(response) {
+            const request = pending.get(response.id);
+            if (!request) {
+                return;
+            }
+            pending.delete(response.id);
+            if (response.ok) {
+                request.resolve(response.data);
+            } else {
+                request.reject(new Error(response.error));
+            }
+        }
+    };
+})();
+
+// Presets de User-Agent dos formulários de perfil ("" = padrão da webview)
+const USER_AGENT_PRESETS = [
+    ['', 'Padrão (WebKitGTK)'],
+    ['Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/121.0.0.0 Safari/537.36', 'Chrome (Windows)'],
+    ['Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/121.0.0.0 Safari/537.36', 'Chrome (Linux)'],
+    ['Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/121.0.0.0 Safari/537.36', 'Chrome (macOS)'],
+    ['Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/121.0.0.0 Safari/537.36 Edg/121.0.0.0', 'Edge (Windows)'],
+    ['Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:122.0) Gecko/20100101 Firefox/122.0', 'Firefox (Windows)'],
+    ['Mozilla/5.0 (X11; Linux x86_64; rv:122.0) Gecko/20100101 Firefox/122.0', 'Firefox (Linux)'],
+    ['Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15', 'Safari (macOS)']
+];
+
+// Preenche o <select> de presets e o mantém em sincronia com o campo de texto.
+// Retorna a sincronização, para quando o campo é preenchido pelo script.
+function setupUserAgentPresets(select, input) {
+    USER_AGENT_PRESETS.forEach(([value, label]) => select.add(new Option(label, value)));
+    select.add(new Option('Personalizado', 'custom'));
+
+    const sync = () => {
+        select.value = USER_AGENT_PRESETS.some(([value]) => value === input.value) ? input.value : 'custom';
+    };
+    select.addEventListener('change', () => {
+        if (select.value !== 'custom') {
+            input.value = select.value;
+        }
+        input.focus();
+    });
+    input.addEventListener('input', sync);
+    return sync;
+}
+
+// Ícones salvos ficam em profiles/{uuid}/ e são servidos pelo protocolo asset,
+// na mesma origem das páginas
+function iconUrl(path) {
+    if (!path || /^(https?:|data:|asset:)/.test(path)) {
+        return path;
+    }
+    return '/' + path.replace(/^\/+/, '');
+}
+
+// Parâmetro da URL da página (ex.: edit-profile.html?uuid=...)
+function pageParam(name) {
+    return new URLSearchParams(window.location.search).get(name);
+}
+
+// Avisos do backend (mensagens Error e Notice) exibidos como toast
+function showToast(message, kind = 'notice') {
+    let container = document.querySelector('.toast-container');
+    if (!container) {
+        container = document.createElement('div');
+        container.className = 'toast-container';
+        document.body.appendChild(container);
+    }
+
+    const toast = document.createElement('div');
+    toast.className = `toast ${kind}`;
+    toast.textContent = message;
+    toast.title = message;
+    toast.setAttribute('role', kind === 'error' ? 'alert' : 'status');
+
+    const dismiss = () => toast.remove();
+    toast.addEventListener('click', dismiss);
+    setTimeout(dismiss, kind === 'error' ? 8000 : 4000);
+
+    container.appendChild(toast);
+}
+
+window.addEventListener('message', (event) => {
+    let message;
+    try {
+        message = JSON.parse(event.data);
+    } catch (e) {
+        return;
+    }
+    if (message.type === 'Error') {
+        showToast(message.payload.message, 'error');
+    } else if (message.type === 'Notice') {
+        showToast(message.payload.message, 'notice');
+    }
+});
@@ -0,0 +1,92 @@
+// Substitui a Web Notification API nas webviews de perfis, encaminhando as
+// notificações para o backend via IPC (exibidas como notificações de desktop).
+(function() {
+    if (!window.ipc || window.__featherNotificationsInstalled) {
+        return;
+    }
+    window.__featherNotificationsInstalled = true;
+
+    function forward(title, options) {
+        options = options || {};
+        window.ipc.postMessage(JSON.stringify({
+            type: 'ShowNotification',
+            payload: {
+                title: String(title || ''),
+                body: options.body ? String(options.body) : ''
+            }
+        }));
+    }
+
+    class FeatherNotification extends EventTarget {
+        constructor(title, options) {
+            super();
+            options = options || {};
+            this.title = String(title || '');
+            this.body = options.body || '';
+            this.tag = options.tag || '';
+            this.icon = options.icon || '';
+            this.data = options.data === undefined ? null : options.data;
+            this.onclick = null;
+            this.onshow = null;
+            this.onclose = null;
+            this.onerror = null;
+
+            forward(this.title, options);
+
+            setTimeout(() => {
+                const event = new Event('show');
+                this.dispatchEvent(event);
+                if (typeof this.onshow === 'function') {
+                    this.onshow(event);
+                }
+            }, 0);
+        }
+
+        close() {
+            const event = new Event('close');
+            this.dispatchEvent(event);
+            if (typeof this.onclose === 'function') {
+                this.onclose(event);
+            }
+        }
+
+        static get permission() {
+            return 'granted';
+        }
+
+        static requestPermission(callback) {
+            if (typeof callback === 'function') {
+                callback('granted');
+            }
+            return Promise.resolve('granted');
+        }
+    }
+
+    Object.defineProperty(window, 'Notification', {
+        value: FeatherNotification,
+        writable: true,
+        configurable: true
+    });
+
+    // Notificações disparadas via service worker registration
+    if (window.ServiceWorkerRegistration) {
+        ServiceWorkerRegistration.prototype.showNotification = function(title, options) {
+            forward(title, options);
+            return Promise.resolve();
+        };
+        ServiceWorkerRegistration.prototype.getNotifications = function() {
+            return Promise.resolve([]);
+        };
+    }
+
+    // Permissions API deve refletir a permissão concedida
+    if (navigator.permissions && navigator.permissions.query) {
+        const originalQuery = navigator.permissions.query.bind(navigator.permissions);
+        navigator.permissions.query = function(descriptor) {
+            if (descriptor && descriptor.name === 'notifications') {
+                return Promise.resolve({ state: 'granted', onchange: null });
+            }
+            return originalQuery(descriptor);
+        };
+    }
+})();
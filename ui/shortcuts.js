@@ -0,0 +1,51 @@
+// Atalhos de teclado da aplicação. Instalado em todas as webviews (inclusive
+// nas dos perfis) para funcionar onde quer que esteja o foco. O backend define
+// window.__featherShortcuts com as combinações ativas e decide a ação.
+(function() {
+    if (!window.ipc || window.__featherShortcutsInstalled) {
+        return;
+    }
+    window.__featherShortcutsInstalled = true;
+
+    const MODIFIER_KEYS = ['Control', 'Alt', 'Shift', 'Meta'];
+
+    // Mesmo formato de shortcuts::normalize_combo: "Ctrl+Alt+Shift+Meta+Tecla"
+    window.__featherShortcutCombo = function(e) {
+        if (MODIFIER_KEYS.includes(e.key)) {
+            return null;
+        }
+        // Números pelo código físico, para Shift+1 não virar "!"
+        let key = /^Digit[0-9]$/.test(e.code) ? e.code.slice(5) : e.key;
+        if (key === ' ') {
+            key = 'Space';
+        } else if (key.length === 1) {
+            key = key.toUpperCase();
+        }
+
+        const parts = [];
+        if (e.ctrlKey) parts.push('Ctrl');
+        if (e.altKey) parts.push('Alt');
+        if (e.shiftKey) parts.push('Shift');
+        if (e.metaKey) parts.push('Meta');
+        parts.push(key);
+        return parts.join('+');
+    };
+
+    // Fase de captura na window: roda antes dos handlers da página
+    window.addEventListener('keydown', function(e) {
+        // A página de configurações pausa os atalhos enquanto grava um novo
+        if (window.__featherShortcutsPaused) {
+            return;
+        }
+        const combo = window.__featherShortcutCombo(e);
+        if (!combo || !(window.__featherShortcuts || []).includes(combo)) {
+            return;
+        }
+        e.preventDefault();
+        e.stopImmediatePropagation();
+        window.ipc.postMessage(JSON.stringify({
+            type: 'Shortcut',
+            payload: { combo }
+        }));
+    }, true);
+})();